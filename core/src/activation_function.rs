@@ -1,4 +1,5 @@
 #[derive(Clone, Copy)]
+#[derive(PartialEq)]
 pub enum ActivationFunction {
     Sigmoid,
     Tanh,
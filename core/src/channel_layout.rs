@@ -0,0 +1,9 @@
+/// How the channels of the elements of a `Tensor` are laid out in memory
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq)]
+pub enum ChannelLayout {
+    /// The channels of every element next to each other (HWC)
+    Interleaved,
+    /// One plane of all elements per channel (CHW)
+    Planar,
+}
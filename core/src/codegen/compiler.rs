@@ -1,16 +1,1497 @@
-use inkwell::OptimizationLevel;
+use std::collections::HashMap;
+
+use inkwell::AddressSpace;
+use inkwell::FloatPredicate;
+use inkwell::IntPredicate;
 use inkwell::builder::Builder;
 use inkwell::context::Context;
-use inkwell::module::Module;
+use inkwell::intrinsics::Intrinsic;
+use inkwell::module::{ Linkage, Module };
+use inkwell::types::{ BasicTypeEnum, FloatType, IntType };
+use inkwell::values::{ ArrayValue, BasicValueEnum, FloatValue, FunctionValue, IntValue, PointerValue };
+
+use super::{
+    Value,
+    Operand,
+    Constant,
+    Node,
+    GeneralType,
+    ScalarType,
+};
+
+use super::layout::Layout;
+use super::fusion::Fusion;
+use crate::Errors;
 
+/// Lowers `Value` graphs into LLVM functions of the form
+/// `void kernel(ptr* parameters, ptr* outputs)`, where every parameter and
+/// output is a pointer to a densely packed buffer of scalars laid out as
+/// `x + y * X + z * X * Y` elements of `channels` interleaved scalars.
 pub struct Compiler<'ctx> {
     context: &'ctx Context,
     module: Module<'ctx>,
     builder: Builder<'ctx>,
 }
 
+#[derive(Clone, Copy)]
+struct Buffer<'ctx> {
+    pointer: PointerValue<'ctx>,
+    layout: Layout,
+}
+
+struct State<'ctx> {
+    function: FunctionValue<'ctx>,
+    parameters: PointerValue<'ctx>,
+    temporaries: Vec<PointerValue<'ctx>>,
+    /// Buffer of every node emitted so far, by id, which stays valid until
+    /// the function returns
+    buffers: HashMap<usize, Buffer<'ctx>>,
+    fusion: Fusion,
+}
+
+/// Chain of element-wise operations evaluated one scalar at a time within a
+/// single loop, reading the buffers of the operands that are not part of it
+enum Fused<'ctx> {
+    Load(Buffer<'ctx>),
+    Arithmetic(Arithmetic, Layout, Box<Fused<'ctx>>, Box<Fused<'ctx>>),
+    Activation(crate::ActivationFunction, Box<Fused<'ctx>>),
+    Derivative(crate::ActivationFunction, Box<Fused<'ctx>>),
+}
+
+#[derive(Clone, Copy)]
+enum Arithmetic {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
 impl<'ctx> Compiler<'ctx> {
-    fn compile(&self, node: super::Node) {
-        todo!()
+    pub fn new(context: &'ctx Context, name: &str) -> Self {
+        Self {
+            context,
+            module: context.create_module(name),
+            builder: context.create_builder(),
+        }
+    }
+
+    pub fn module(&self) -> &Module<'ctx> {
+        &self.module
+    }
+
+    /// Emits a function named `name` writing each of `outputs` into the
+    /// buffer at the matching index of its second argument
+    pub fn compile(&self, name: &str, outputs: &[&Value]) -> crate::Result<FunctionValue<'ctx>> {
+        let pointer_type = self.context.ptr_type(AddressSpace::default());
+        let function_type = self.context.void_type()
+            .fn_type(&[pointer_type.into(), pointer_type.into()], false);
+
+        let function = self.module.add_function(name, function_type, None);
+        let entry = self.context.append_basic_block(function, "entry");
+        self.builder.position_at_end(entry);
+
+        let (parameters, destinations) = match (function.get_nth_param(0), function.get_nth_param(1)) {
+            (Some(parameters), Some(destinations)) => (parameters.into_pointer_value(), destinations.into_pointer_value()),
+            _ => return Errors::CompilationFailed.into(),
+        };
+
+        let mut state = State {
+            function,
+            parameters,
+            temporaries: Vec::new(),
+            buffers: HashMap::new(),
+            fusion: Fusion::plan(outputs),
+        };
+
+        for (index, output) in outputs.iter().enumerate() {
+            let pointer = self.load_pointer(destinations, index as u64)?;
+            let source = self.emit_into(output, Some(pointer), &mut state)?;
+
+            // Outputs emitted into a buffer of their own are copied out
+            if source.pointer != pointer {
+                let destination = Buffer { pointer, layout: source.layout };
+
+                self.build_loop(&state, source.layout.len(), &mut |i| {
+                    let value = self.load(&source, i)?;
+                    self.store(&destination, i, value)
+                })?;
+            }
+        }
+
+        for temporary in state.temporaries {
+            self.builder.build_free(temporary)?;
+        }
+
+        self.builder.build_return(None)?;
+
+        if !function.verify(false) {
+            return Errors::CompilationFailed.into();
+        }
+
+        self.module.verify()
+            .map_err(|_| crate::Error::from(Errors::CompilationFailed))?;
+
+        Ok(function)
+    }
+
+    /// Emits `value`, nodes shared by several values only once
+    fn emit(&self, value: &Value, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        self.emit_into(value, None, state)
+    }
+
+    /// Emits `value` like `emit`, element-wise chains and matrix products
+    /// not emitted yet storing their scalars straight into `destination`,
+    /// where the nodes reading them later find them too
+    fn emit_into(&self, value: &Value, destination: Option<PointerValue<'ctx>>, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let id = match value.id() {
+            Some(id) => id,
+            None => return self.emit_value(value, destination, state),
+        };
+
+        if let Some(buffer) = state.buffers.get(&id) {
+            return Ok(*buffer);
+        }
+
+        let buffer = self.emit_value(value, destination, state)?;
+        state.buffers.insert(id, buffer);
+
+        Ok(buffer)
+    }
+
+    fn emit_value(&self, value: &Value, destination: Option<PointerValue<'ctx>>, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let layout = Layout::of(value);
+
+        match &value.inner {
+            Operand::Parameter(index, _) => Ok(Buffer {
+                pointer: self.load_pointer(state.parameters, *index as u64)?,
+                layout,
+            }),
+            Operand::Constant(constant) => self.emit_constant(constant, layout, state),
+            Operand::Node(node) => match node.as_ref() {
+                Node::Add(..) | Node::Subtract(..) | Node::HadamardProduct(..) | Node::Divide(..)
+                    | Node::Sigmoid(_) | Node::Tanh(_) | Node::Relu(_) | Node::LeakyRelu(..)
+                    | Node::Elu(_) | Node::Swish(_) | Node::Softplus(..) | Node::Derivative(..) => {
+                    self.emit_fused(value, layout, destination, state)
+                },
+                Node::Multiply(a, b) => match (a.general_type, b.general_type) {
+                    (GeneralType::Tensor(..), GeneralType::Tensor(..)) => self.emit_matrix_product(a, b, layout, destination, state),
+                    _ => self.emit_fused(value, layout, destination, state),
+                },
+                Node::Convolve(operand, _, stride) => self.emit_convolve(operand, *stride, layout, state),
+                Node::ConvergeSum(operand) => self.emit_converge_sum(operand, layout, state),
+                Node::Transpose(operand) => self.emit_transpose(operand, layout, state),
+                Node::Sum(operand) => self.emit_sum(operand, layout, state),
+                Node::Broadcast(operand) => self.emit_broadcast(operand, layout, state),
+                Node::Accumulate(operand) => self.emit_accumulate(operand, layout, state),
+                Node::Spread(operand) => self.emit_spread(operand, layout, state),
+                Node::Deconvolve(operand, _, stride) => self.emit_deconvolve(operand, *stride, layout, state),
+                Node::Reshape(operand) => {
+                    // Same scalars in the same order, only relabelled
+                    let input = self.emit(operand, state)?;
+                    Ok(Buffer { pointer: input.pointer, layout })
+                },
+                Node::Pad(operand, padding) => self.emit_pad(operand, *padding, layout, state),
+                Node::Crop(operand, padding) => self.emit_crop(operand, *padding, layout, state),
+                Node::Permute(operand, axes) => self.emit_permute(operand, axes, layout, state),
+                Node::Concat(a, b, axis) => self.emit_concat(a, b, *axis, layout, state),
+                Node::Slice(operand, axis, start) => self.emit_slice(operand, *axis, *start, layout, state),
+                Node::Loss(loss, prediction, target) => self.emit_loss(loss, prediction, target, layout, state),
+                Node::LossGradient(loss, prediction, target) => self.emit_loss_gradient(loss, prediction, target, layout, state),
+            },
+        }
+    }
+
+    fn emit_constant(&self, constant: &Constant, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let value: BasicValueEnum<'ctx> = match constant {
+            Constant::ScalarF32(value) => self.context.f32_type().const_float(*value as f64).into(),
+            Constant::ScalarF64(value) => self.context.f64_type().const_float(*value).into(),
+            Constant::ScalarU8(value) => self.context.i8_type().const_int(*value as u64, false).into(),
+            Constant::ScalarU16(value) => self.context.i16_type().const_int(*value as u64, false).into(),
+            Constant::ScalarU32(value) => self.context.i32_type().const_int(*value as u64, false).into(),
+            Constant::ScalarU64(value) => self.context.i64_type().const_int(*value, false).into(),
+            Constant::ScalarI8(value) => self.context.i8_type().const_int(*value as u64, true).into(),
+            Constant::ScalarI16(value) => self.context.i16_type().const_int(*value as u64, true).into(),
+            Constant::ScalarI32(value) => self.context.i32_type().const_int(*value as u64, true).into(),
+            Constant::ScalarI64(value) => self.context.i64_type().const_int(*value as u64, true).into(),
+            _ => return self.emit_global(constant, layout),
+        };
+
+        let buffer = self.allocate(layout, state)?;
+        self.store(&buffer, self.index(0), value)?;
+
+        Ok(buffer)
+    }
+
+    /// Embeds the scalars of an element or tensor constant in a private
+    /// global, read in place like a parameter
+    fn emit_global(&self, constant: &Constant, layout: Layout) -> crate::Result<Buffer<'ctx>> {
+        let array = match constant {
+            Constant::ElementF32(element) => self.float_array(self.context.f32_type(), element.as_slice().iter().map(|x| *x as f64)),
+            Constant::ElementF64(element) => self.float_array(self.context.f64_type(), element.as_slice().iter().copied()),
+            Constant::ElementU8(element) => self.int_array(self.context.i8_type(), element.as_slice().iter().map(|x| *x as u64), false),
+            Constant::ElementU16(element) => self.int_array(self.context.i16_type(), element.as_slice().iter().map(|x| *x as u64), false),
+            Constant::ElementU32(element) => self.int_array(self.context.i32_type(), element.as_slice().iter().map(|x| *x as u64), false),
+            Constant::ElementU64(element) => self.int_array(self.context.i64_type(), element.as_slice().iter().copied(), false),
+            Constant::ElementI8(element) => self.int_array(self.context.i8_type(), element.as_slice().iter().map(|x| *x as u64), true),
+            Constant::ElementI16(element) => self.int_array(self.context.i16_type(), element.as_slice().iter().map(|x| *x as u64), true),
+            Constant::ElementI32(element) => self.int_array(self.context.i32_type(), element.as_slice().iter().map(|x| *x as u64), true),
+            Constant::ElementI64(element) => self.int_array(self.context.i64_type(), element.as_slice().iter().map(|x| *x as u64), true),
+            Constant::TensorF32(tensor) => self.float_array(self.context.f32_type(), tensor.as_slice().iter().map(|x| *x as f64)),
+            Constant::TensorF64(tensor) => self.float_array(self.context.f64_type(), tensor.as_slice().iter().copied()),
+            Constant::TensorU8(tensor) => self.int_array(self.context.i8_type(), tensor.as_slice().iter().map(|x| *x as u64), false),
+            Constant::TensorU16(tensor) => self.int_array(self.context.i16_type(), tensor.as_slice().iter().map(|x| *x as u64), false),
+            Constant::TensorU32(tensor) => self.int_array(self.context.i32_type(), tensor.as_slice().iter().map(|x| *x as u64), false),
+            Constant::TensorU64(tensor) => self.int_array(self.context.i64_type(), tensor.as_slice().iter().copied(), false),
+            Constant::TensorI8(tensor) => self.int_array(self.context.i8_type(), tensor.as_slice().iter().map(|x| *x as u64), true),
+            Constant::TensorI16(tensor) => self.int_array(self.context.i16_type(), tensor.as_slice().iter().map(|x| *x as u64), true),
+            Constant::TensorI32(tensor) => self.int_array(self.context.i32_type(), tensor.as_slice().iter().map(|x| *x as u64), true),
+            Constant::TensorI64(tensor) => self.int_array(self.context.i64_type(), tensor.as_slice().iter().map(|x| *x as u64), true),
+            _ => return Errors::UnsupportedOperand.into(),
+        };
+
+        let global = self.module.add_global(array.get_type(), None, "constant");
+        global.set_initializer(&array);
+        global.set_constant(true);
+        global.set_linkage(Linkage::Private);
+
+        Ok(Buffer {
+            pointer: global.as_pointer_value(),
+            layout,
+        })
     }
-}
\ No newline at end of file
+
+    fn float_array(&self, float_type: FloatType<'ctx>, values: impl Iterator<Item = f64>) -> ArrayValue<'ctx> {
+        let values: Vec<FloatValue<'ctx>> = values.map(|value| float_type.const_float(value)).collect();
+        float_type.const_array(&values)
+    }
+
+    fn int_array(&self, int_type: IntType<'ctx>, values: impl Iterator<Item = u64>, signed: bool) -> ArrayValue<'ctx> {
+        let values: Vec<IntValue<'ctx>> = values.map(|value| int_type.const_int(value, signed)).collect();
+        int_type.const_array(&values)
+    }
+
+    /// Emits an element-wise node in a single loop along with the operands
+    /// fused into it
+    fn emit_fused(&self, value: &Value, layout: Layout, destination: Option<PointerValue<'ctx>>, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let fused = self.fuse(value, layout, state)?;
+        let output = self.output(layout, destination, state)?;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            let value = self.evaluate(&fused, &layout, i)?;
+            self.store(&output, i, value)
+        })?;
+
+        Ok(output)
+    }
+
+    /// Expands element-wise `value` and the operands inlined into it,
+    /// emitting the buffers of the others
+    fn fuse(&self, value: &Value, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Fused<'ctx>> {
+        let node = match &value.inner {
+            Operand::Node(node) => node,
+            _ => return Errors::UnsupportedOperand.into(),
+        };
+
+        let arithmetic = |operation, a: &Value, b: &Value, state: &mut State<'ctx>| -> crate::Result<Fused<'ctx>> {
+            Ok(Fused::Arithmetic(operation, layout, Box::new(self.fuse_operand(a, state)?), Box::new(self.fuse_operand(b, state)?)))
+        };
+
+        // Activations are only defined over floats
+        let float = |operand: &Value, state: &mut State<'ctx>| -> crate::Result<Box<Fused<'ctx>>> {
+            if !layout.scalar.is_float() {
+                return Errors::InvalidOperandTypes.into();
+            }
+
+            Ok(Box::new(self.fuse_operand(operand, state)?))
+        };
+
+        Ok(match node.as_ref() {
+            Node::Add(a, b) => arithmetic(Arithmetic::Add, a, b, state)?,
+            Node::Subtract(a, b) => arithmetic(Arithmetic::Subtract, a, b, state)?,
+            Node::HadamardProduct(a, b) | Node::Multiply(a, b) => arithmetic(Arithmetic::Multiply, a, b, state)?,
+            Node::Divide(a, b) => arithmetic(Arithmetic::Divide, a, b, state)?,
+            Node::Sigmoid(a) => Fused::Activation(crate::ActivationFunction::Sigmoid, float(a, state)?),
+            Node::Tanh(a) => Fused::Activation(crate::ActivationFunction::Tanh, float(a, state)?),
+            Node::Relu(a) => Fused::Activation(crate::ActivationFunction::Relu, float(a, state)?),
+            Node::LeakyRelu(a, beta) => Fused::Activation(crate::ActivationFunction::LeakyRelu(*beta), float(a, state)?),
+            Node::Elu(a) => Fused::Activation(crate::ActivationFunction::Elu, float(a, state)?),
+            Node::Swish(a) => Fused::Activation(crate::ActivationFunction::Swish, float(a, state)?),
+            Node::Softplus(a, beta) => Fused::Activation(crate::ActivationFunction::Softplus(*beta), float(a, state)?),
+            Node::Derivative(activation_fn, a) => Fused::Derivative(*activation_fn, float(a, state)?),
+            _ => return Errors::UnsupportedOperand.into(),
+        })
+    }
+
+    fn fuse_operand(&self, operand: &Value, state: &mut State<'ctx>) -> crate::Result<Fused<'ctx>> {
+        if state.fusion.is_inlined(operand) {
+            self.fuse(operand, Layout::of(operand), state)
+        } else {
+            Ok(Fused::Load(self.emit(operand, state)?))
+        }
+    }
+
+    /// Scalar at `index` of `fused`, laid out as `layout`
+    fn evaluate(&self, fused: &Fused<'ctx>, layout: &Layout, index: IntValue<'ctx>) -> crate::Result<BasicValueEnum<'ctx>> {
+        match fused {
+            Fused::Load(buffer) => if buffer.layout == *layout {
+                self.load(buffer, index)
+            } else {
+                self.load(buffer, self.broadcast(&buffer.layout, layout, index)?)
+            },
+            // Inlined operands share the layout of the node reading them
+            Fused::Arithmetic(operation, layout, a, b) => {
+                let x = self.evaluate(a, layout, index)?;
+                let y = self.evaluate(b, layout, index)?;
+
+                self.arithmetic(*operation, layout.scalar, x, y)
+            },
+            Fused::Activation(activation_fn, operand) => {
+                let x = self.evaluate(operand, layout, index)?.into_float_value();
+                Ok(self.activation(activation_fn, x)?.into())
+            },
+            Fused::Derivative(activation_fn, operand) => {
+                let x = self.evaluate(operand, layout, index)?.into_float_value();
+                Ok(self.derivative(activation_fn, x)?.into())
+            },
+        }
+    }
+
+    fn emit_matrix_product(&self, a: &Value, b: &Value, layout: Layout, destination: Option<PointerValue<'ctx>>, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let a = self.emit(a, state)?;
+        let b = self.emit(b, state)?;
+        let output = self.output(layout, destination, state)?;
+
+        let channels = layout.channels;
+        let (rows, columns, depth) = layout.shape;
+        let shared = a.layout.shape.1;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            let channel = self.builder.build_int_unsigned_rem(i, self.index(channels), "channel")?;
+            let rest = self.builder.build_int_unsigned_div(i, self.index(channels), "rest")?;
+            let row = self.builder.build_int_unsigned_rem(rest, self.index(rows), "row")?;
+            let rest = self.builder.build_int_unsigned_div(rest, self.index(rows), "rest")?;
+            let column = self.builder.build_int_unsigned_rem(rest, self.index(columns), "column")?;
+            let rest = self.builder.build_int_unsigned_div(rest, self.index(columns), "rest")?;
+            let plane = self.builder.build_int_unsigned_rem(rest, self.index(depth), "plane")?;
+            let block = self.builder.build_int_unsigned_div(rest, self.index(depth), "block")?;
+
+            let a_base = self.block_base(&a.layout, &layout, block)?;
+            let b_base = self.block_base(&b.layout, &layout, block)?;
+
+            self.store(&output, i, self.zero(layout.scalar))?;
+
+            self.build_loop(state, shared, &mut |k| {
+                // a[row, k, plane] and b[k, column, plane]
+                let a_offset = self.element_offset(&a.layout, row, k, plane, channel, a_base)?;
+                let b_offset = self.element_offset(&b.layout, k, column, plane, channel, b_base)?;
+
+                let product = self.arithmetic(Arithmetic::Multiply, layout.scalar,
+                    self.load(&a, a_offset)?,
+                    self.load(&b, b_offset)?)?;
+
+                let sum = self.arithmetic(Arithmetic::Add, layout.scalar, self.load(&output, i)?, product)?;
+                self.store(&output, i, sum)
+            })
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_convolve(&self, operand: &Value, stride: (u32, u32), layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        let channels = layout.channels;
+        let (width, height, depth) = layout.shape;
+        let positions_x = (input.layout.shape.0 - width) / stride.0 as u64 + 1;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            let channel = self.builder.build_int_unsigned_rem(i, self.index(channels), "channel")?;
+            let rest = self.builder.build_int_unsigned_div(i, self.index(channels), "rest")?;
+            let x = self.builder.build_int_unsigned_rem(rest, self.index(width), "x")?;
+            let rest = self.builder.build_int_unsigned_div(rest, self.index(width), "rest")?;
+            let y = self.builder.build_int_unsigned_rem(rest, self.index(height), "y")?;
+            let rest = self.builder.build_int_unsigned_div(rest, self.index(height), "rest")?;
+            let plane = self.builder.build_int_unsigned_rem(rest, self.index(depth), "plane")?;
+            let block = self.builder.build_int_unsigned_div(rest, self.index(depth), "block")?;
+            let window = self.builder.build_int_unsigned_rem(block, self.index(layout.outer), "window")?;
+
+            let window_x = self.builder.build_int_unsigned_rem(window, self.index(positions_x), "window_x")?;
+            let window_y = self.builder.build_int_unsigned_div(window, self.index(positions_x), "window_y")?;
+
+            let x = self.builder.build_int_add(
+                self.builder.build_int_mul(window_x, self.index(stride.0 as u64), "")?, x, "input_x")?;
+            let y = self.builder.build_int_add(
+                self.builder.build_int_mul(window_y, self.index(stride.1 as u64), "")?, y, "input_y")?;
+
+            let base = self.block_base(&input.layout, &layout, block)?;
+            let offset = self.element_offset(&input.layout, x, y, plane, channel, base)?;
+            self.store(&output, i, self.load(&input, offset)?)
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_converge_sum(&self, operand: &Value, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        let channels = layout.channels;
+        let (positions_x, positions_y, depth) = layout.shape;
+        let area = input.layout.shape.0 * input.layout.shape.1;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            let channel = self.builder.build_int_unsigned_rem(i, self.index(channels), "channel")?;
+            let rest = self.builder.build_int_unsigned_div(i, self.index(channels), "rest")?;
+            let window_x = self.builder.build_int_unsigned_rem(rest, self.index(positions_x), "window_x")?;
+            let rest = self.builder.build_int_unsigned_div(rest, self.index(positions_x), "rest")?;
+            let window_y = self.builder.build_int_unsigned_rem(rest, self.index(positions_y), "window_y")?;
+            let rest = self.builder.build_int_unsigned_div(rest, self.index(positions_y), "rest")?;
+            let plane = self.builder.build_int_unsigned_rem(rest, self.index(depth), "plane")?;
+            let sample = self.builder.build_int_unsigned_div(rest, self.index(depth), "sample")?;
+
+            // Windows of a sample follow each other
+            let window = self.builder.build_int_add(window_x,
+                self.builder.build_int_mul(window_y, self.index(positions_x), "")?, "window")?;
+            let window = self.builder.build_int_add(window,
+                self.builder.build_int_mul(sample, self.index(input.layout.outer), "")?, "window")?;
+            let base = self.builder.build_int_mul(window, self.index(input.layout.inner()), "base")?;
+            let base = self.builder.build_int_add(base,
+                self.builder.build_int_mul(plane, self.index(area * channels), "")?, "base")?;
+            let base = self.builder.build_int_add(base, channel, "base")?;
+
+            self.store(&output, i, self.zero(layout.scalar))?;
+
+            self.build_loop(state, area, &mut |k| {
+                let offset = self.builder.build_int_add(base,
+                    self.builder.build_int_mul(k, self.index(channels), "")?, "offset")?;
+
+                let sum = self.arithmetic(Arithmetic::Add, layout.scalar,
+                    self.load(&output, i)?,
+                    self.load(&input, offset)?)?;
+
+                self.store(&output, i, sum)
+            })
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_transpose(&self, operand: &Value, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        let channels = layout.channels;
+        let (rows, columns, depth) = layout.shape;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            let channel = self.builder.build_int_unsigned_rem(i, self.index(channels), "channel")?;
+            let rest = self.builder.build_int_unsigned_div(i, self.index(channels), "rest")?;
+            let row = self.builder.build_int_unsigned_rem(rest, self.index(rows), "row")?;
+            let rest = self.builder.build_int_unsigned_div(rest, self.index(rows), "rest")?;
+            let column = self.builder.build_int_unsigned_rem(rest, self.index(columns), "column")?;
+            let rest = self.builder.build_int_unsigned_div(rest, self.index(columns), "rest")?;
+            let plane = self.builder.build_int_unsigned_rem(rest, self.index(depth), "plane")?;
+            let block = self.builder.build_int_unsigned_div(rest, self.index(depth), "block")?;
+
+            let base = self.block_base(&input.layout, &layout, block)?;
+            let offset = self.element_offset(&input.layout, column, row, plane, channel, base)?;
+
+            self.store(&output, i, self.load(&input, offset)?)
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_sum(&self, operand: &Value, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        let channels = layout.channels;
+        let count = input.layout.shape.0 * input.layout.shape.1 * input.layout.shape.2;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            let channel = self.builder.build_int_unsigned_rem(i, self.index(channels), "channel")?;
+            let block = self.builder.build_int_unsigned_div(i, self.index(channels), "block")?;
+            let base = self.builder.build_int_add(self.block_base(&input.layout, &layout, block)?, channel, "base")?;
+
+            self.store(&output, i, self.zero(layout.scalar))?;
+
+            self.build_loop(state, count, &mut |k| {
+                let offset = self.builder.build_int_add(base,
+                    self.builder.build_int_mul(k, self.index(channels), "")?, "offset")?;
+
+                let sum = self.arithmetic(Arithmetic::Add, layout.scalar,
+                    self.load(&output, i)?,
+                    self.load(&input, offset)?)?;
+
+                self.store(&output, i, sum)
+            })
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_broadcast(&self, operand: &Value, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            let offset = self.broadcast(&input.layout, &layout, i)?;
+            self.store(&output, i, self.load(&input, offset)?)
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_accumulate(&self, operand: &Value, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            self.store(&output, i, self.zero(layout.scalar))
+        })?;
+
+        // Every scalar adds onto the one it would be broadcast from
+        self.build_loop(state, input.layout.len(), &mut |i| {
+            let offset = self.broadcast(&layout, &input.layout, i)?;
+
+            let sum = self.arithmetic(Arithmetic::Add, layout.scalar,
+                self.load(&output, offset)?,
+                self.load(&input, i)?)?;
+
+            self.store(&output, offset, sum)
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_spread(&self, operand: &Value, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        let channels = layout.channels;
+        let (width, height, depth) = layout.shape;
+        let positions_x = input.layout.shape.0;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            let channel = self.builder.build_int_unsigned_rem(i, self.index(channels), "channel")?;
+            let rest = self.builder.build_int_unsigned_div(i, self.index(channels * width * height), "rest")?;
+            let plane = self.builder.build_int_unsigned_rem(rest, self.index(depth), "plane")?;
+            let block = self.builder.build_int_unsigned_div(rest, self.index(depth), "block")?;
+            let window = self.builder.build_int_unsigned_rem(block, self.index(layout.outer), "window")?;
+
+            let window_x = self.builder.build_int_unsigned_rem(window, self.index(positions_x), "window_x")?;
+            let window_y = self.builder.build_int_unsigned_div(window, self.index(positions_x), "window_y")?;
+
+            let base = self.block_base(&input.layout, &layout, block)?;
+            let offset = self.element_offset(&input.layout, window_x, window_y, plane, channel, base)?;
+            self.store(&output, i, self.load(&input, offset)?)
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_deconvolve(&self, operand: &Value, stride: (u32, u32), layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        let channels = layout.channels;
+        let (width, height, depth) = input.layout.shape;
+        let positions_x = (layout.shape.0 - width) / stride.0 as u64 + 1;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            self.store(&output, i, self.zero(layout.scalar))
+        })?;
+
+        self.build_loop(state, input.layout.len(), &mut |i| {
+            let channel = self.builder.build_int_unsigned_rem(i, self.index(channels), "channel")?;
+            let rest = self.builder.build_int_unsigned_div(i, self.index(channels), "rest")?;
+            let x = self.builder.build_int_unsigned_rem(rest, self.index(width), "x")?;
+            let rest = self.builder.build_int_unsigned_div(rest, self.index(width), "rest")?;
+            let y = self.builder.build_int_unsigned_rem(rest, self.index(height), "y")?;
+            let rest = self.builder.build_int_unsigned_div(rest, self.index(height), "rest")?;
+            let plane = self.builder.build_int_unsigned_rem(rest, self.index(depth), "plane")?;
+            let block = self.builder.build_int_unsigned_div(rest, self.index(depth), "block")?;
+            let window = self.builder.build_int_unsigned_rem(block, self.index(input.layout.outer), "window")?;
+
+            let window_x = self.builder.build_int_unsigned_rem(window, self.index(positions_x), "window_x")?;
+            let window_y = self.builder.build_int_unsigned_div(window, self.index(positions_x), "window_y")?;
+
+            let x = self.builder.build_int_add(
+                self.builder.build_int_mul(window_x, self.index(stride.0 as u64), "")?, x, "output_x")?;
+            let y = self.builder.build_int_add(
+                self.builder.build_int_mul(window_y, self.index(stride.1 as u64), "")?, y, "output_y")?;
+
+            let base = self.block_base(&output.layout, &input.layout, block)?;
+            let offset = self.element_offset(&output.layout, x, y, plane, channel, base)?;
+            let sum = self.arithmetic(Arithmetic::Add, layout.scalar,
+                self.load(&output, offset)?,
+                self.load(&input, i)?)?;
+
+            self.store(&output, offset, sum)
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_pad(&self, operand: &Value, padding: (u32, u32), layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            self.store(&output, i, self.zero(layout.scalar))
+        })?;
+
+        // Every input element lands `padding` further along x and y
+        self.build_loop(state, input.layout.len(), &mut |i| {
+            let offset = self.shift(&input.layout, &layout, i, (padding.0 as u64, padding.1 as u64, 0))?;
+            self.store(&output, offset, self.load(&input, i)?)
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_crop(&self, operand: &Value, padding: (u32, u32), layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            let offset = self.shift(&layout, &input.layout, i, (padding.0 as u64, padding.1 as u64, 0))?;
+            self.store(&output, i, self.load(&input, offset)?)
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_permute(&self, operand: &Value, axes: &[u32], layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        let channels = layout.channels;
+        let sizes = layout.dimension.axes();
+
+        // Stride of every axis of the input, in elements
+        let mut strides = vec![0; input.layout.dimension.rank()];
+        let mut stride = 1;
+
+        for (axis, size) in input.layout.dimension.axes().iter().enumerate() {
+            strides[axis] = stride;
+            stride *= *size as u64;
+        }
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            let channel = self.builder.build_int_unsigned_rem(i, self.index(channels), "channel")?;
+            let mut rest = self.builder.build_int_unsigned_div(i, self.index(channels), "rest")?;
+            let mut element = self.index(0);
+
+            // Axis `k` of the output is axis `axes[k]` of the input
+            for (k, size) in sizes.iter().enumerate() {
+                let position = self.builder.build_int_unsigned_rem(rest, self.index(*size as u64), "position")?;
+                rest = self.builder.build_int_unsigned_div(rest, self.index(*size as u64), "rest")?;
+
+                element = self.builder.build_int_add(element,
+                    self.builder.build_int_mul(position, self.index(strides[axes[k] as usize]), "")?, "element")?;
+            }
+
+            let base = self.block_base(&input.layout, &layout, rest)?;
+            let offset = self.builder.build_int_add(base,
+                self.builder.build_int_mul(element, self.index(channels), "")?, "offset")?;
+            let offset = self.builder.build_int_add(offset, channel, "offset")?;
+
+            self.store(&output, i, self.load(&input, offset)?)
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_concat(&self, a: &Value, b: &Value, axis: u32, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let a = self.emit(a, state)?;
+        let b = self.emit(b, state)?;
+        let output = self.allocate(layout, state)?;
+
+        // `b` starts where `a` ends along the axis
+        let end = a.layout.dimension.axis(axis as usize) as u64;
+        let around = layout.around(axis);
+
+        self.build_loop(state, a.layout.len(), &mut |i| {
+            let offset = self.shift(&a.layout.around(axis), &around, i, (0, 0, 0))?;
+            self.store(&output, offset, self.load(&a, i)?)
+        })?;
+
+        self.build_loop(state, b.layout.len(), &mut |i| {
+            let offset = self.shift(&b.layout.around(axis), &around, i, (0, end, 0))?;
+            self.store(&output, offset, self.load(&b, i)?)
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_slice(&self, operand: &Value, axis: u32, start: u32, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let input = self.emit(operand, state)?;
+        let output = self.allocate(layout, state)?;
+
+        let (from, to) = (layout.around(axis), input.layout.around(axis));
+
+        self.build_loop(state, layout.len(), &mut |i| {
+            let offset = self.shift(&from, &to, i, (0, start as u64, 0))?;
+            self.store(&output, i, self.load(&input, offset)?)
+        })?;
+
+        Ok(output)
+    }
+
+    /// Maps an index into `from` onto the index into `to` of the element
+    /// moved `shift` further along each axis
+    fn shift(&self, from: &Layout, to: &Layout, index: IntValue<'ctx>, shift: (u64, u64, u64)) -> crate::Result<IntValue<'ctx>> {
+        let channels = from.channels;
+        let (width, height, depth) = from.shape;
+
+        let channel = self.builder.build_int_unsigned_rem(index, self.index(channels), "channel")?;
+        let rest = self.builder.build_int_unsigned_div(index, self.index(channels), "rest")?;
+        let x = self.builder.build_int_unsigned_rem(rest, self.index(width), "x")?;
+        let rest = self.builder.build_int_unsigned_div(rest, self.index(width), "rest")?;
+        let y = self.builder.build_int_unsigned_rem(rest, self.index(height), "y")?;
+        let rest = self.builder.build_int_unsigned_div(rest, self.index(height), "rest")?;
+        let z = self.builder.build_int_unsigned_rem(rest, self.index(depth), "z")?;
+        let block = self.builder.build_int_unsigned_div(rest, self.index(depth), "block")?;
+
+        let x = self.builder.build_int_add(x, self.index(shift.0), "shifted_x")?;
+        let y = self.builder.build_int_add(y, self.index(shift.1), "shifted_y")?;
+        let z = self.builder.build_int_add(z, self.index(shift.2), "shifted_z")?;
+
+        let base = self.block_base(to, from, block)?;
+        self.element_offset(to, x, y, z, channel, base)
+    }
+
+    fn emit_loss(&self, loss: &crate::Loss, prediction: &Value, target: &Value, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let prediction = self.emit(prediction, state)?;
+        let target = self.emit(target, state)?;
+        let output = self.allocate(layout, state)?;
+        let statistics = self.allocate_statistics(layout.scalar, state)?;
+
+        let count = prediction.layout.inner();
+        let float_type = self.scalar_type(layout.scalar).into_float_type();
+
+        // One loss for every sample
+        self.build_loop(state, layout.batch, &mut |sample| {
+            let prediction_base = self.sample_base(&prediction.layout, sample)?;
+            let target_base = self.sample_base(&target.layout, sample)?;
+            let values = |i| -> crate::Result<(FloatValue<'ctx>, FloatValue<'ctx>)> {
+                let x = self.load(&prediction, self.builder.build_int_add(prediction_base, i, "")?)?;
+                let y = self.load(&target, self.builder.build_int_add(target_base, i, "")?)?;
+
+                Ok((x.into_float_value(), y.into_float_value()))
+            };
+
+            self.store(&output, sample, self.zero(layout.scalar))?;
+
+            match loss {
+                crate::Loss::CategoricalCrossEntropy => {
+                    // sum(t * (logsumexp(p) - p))
+                    self.emit_softmax_statistics(&statistics, count, state, &values)?;
+                    let maximum = self.load(&statistics, self.index(0))?.into_float_value();
+                    let exponents = self.load(&statistics, self.index(1))?.into_float_value();
+                    let log_sum = self.builder.build_float_add(maximum, self.log(exponents)?, "log_sum")?;
+
+                    self.build_loop(state, count, &mut |i| {
+                        let (x, y) = values(i)?;
+                        let term = self.builder.build_float_mul(y,
+                            self.builder.build_float_sub(log_sum, x, "")?, "term")?;
+
+                        let sum = self.builder.build_float_add(self.load(&output, sample)?.into_float_value(), term, "sum")?;
+                        self.store(&output, sample, sum.into())
+                    })
+                },
+                _ => {
+                    self.build_loop(state, count, &mut |i| {
+                        let (x, y) = values(i)?;
+                        let term = self.loss(loss, x, y)?;
+
+                        let sum = self.builder.build_float_add(self.load(&output, sample)?.into_float_value(), term, "sum")?;
+                        self.store(&output, sample, sum.into())
+                    })?;
+
+                    let mean = self.builder.build_float_div(self.load(&output, sample)?.into_float_value(),
+                        float_type.const_float(count as f64), "mean")?;
+                    self.store(&output, sample, mean.into())
+                },
+            }
+        })?;
+
+        Ok(output)
+    }
+
+    fn emit_loss_gradient(&self, loss: &crate::Loss, prediction: &Value, target: &Value, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let prediction = self.emit(prediction, state)?;
+        let target = self.emit(target, state)?;
+        let output = self.allocate(layout, state)?;
+        let statistics = self.allocate_statistics(layout.scalar, state)?;
+
+        let count = layout.inner();
+        let float_type = self.scalar_type(layout.scalar).into_float_type();
+
+        self.build_loop(state, layout.batch, &mut |sample| {
+            let prediction_base = self.sample_base(&prediction.layout, sample)?;
+            let target_base = self.sample_base(&target.layout, sample)?;
+            let output_base = self.sample_base(&layout, sample)?;
+            let values = |i| -> crate::Result<(FloatValue<'ctx>, FloatValue<'ctx>)> {
+                let x = self.load(&prediction, self.builder.build_int_add(prediction_base, i, "")?)?;
+                let y = self.load(&target, self.builder.build_int_add(target_base, i, "")?)?;
+
+                Ok((x.into_float_value(), y.into_float_value()))
+            };
+
+            match loss {
+                crate::Loss::CategoricalCrossEntropy => {
+                    // softmax(p) * sum(t) - t
+                    self.emit_softmax_statistics(&statistics, count, state, &values)?;
+                    let maximum = self.load(&statistics, self.index(0))?.into_float_value();
+                    let exponents = self.load(&statistics, self.index(1))?.into_float_value();
+                    let targets = self.load(&statistics, self.index(2))?.into_float_value();
+
+                    self.build_loop(state, count, &mut |i| {
+                        let (x, y) = values(i)?;
+                        let softmax = self.builder.build_float_div(
+                            self.exp(self.builder.build_float_sub(x, maximum, "")?)?, exponents, "softmax")?;
+                        let gradient = self.builder.build_float_sub(
+                            self.builder.build_float_mul(softmax, targets, "")?, y, "gradient")?;
+
+                        self.store(&output, self.builder.build_int_add(output_base, i, "")?, gradient.into())
+                    })
+                },
+                _ => {
+                    let count_value = float_type.const_float(count as f64);
+
+                    self.build_loop(state, count, &mut |i| {
+                        let (x, y) = values(i)?;
+                        let gradient = self.builder.build_float_div(self.loss_derivative(loss, x, y)?, count_value, "gradient")?;
+
+                        self.store(&output, self.builder.build_int_add(output_base, i, "")?, gradient.into())
+                    })
+                },
+            }
+        })?;
+
+        Ok(output)
+    }
+
+    /// Room for the maximum of a prediction, the sum of `e^(p - maximum)`
+    /// over it and the sum of its target, in that order
+    fn allocate_statistics(&self, scalar: ScalarType, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        self.allocate(Layout {
+            batch: 1,
+            outer: 1,
+            shape: (1, 1, 1),
+            dimension: crate::Dimension::new(1, 1, 1),
+            channels: 3,
+            scalar,
+            element: true,
+        }, state)
+    }
+
+    /// Fills `statistics` for the `count` prediction and target pairs read
+    /// by `values`
+    fn emit_softmax_statistics(&self, statistics: &Buffer<'ctx>, count: u64, state: &State<'ctx>,
+        values: &dyn Fn(IntValue<'ctx>) -> crate::Result<(FloatValue<'ctx>, FloatValue<'ctx>)>) -> crate::Result<()> {
+
+        let scalar = statistics.layout.scalar;
+        let (maximum, exponents, targets) = (self.index(0), self.index(1), self.index(2));
+
+        self.store(statistics, maximum, values(self.index(0))?.0.into())?;
+        self.store(statistics, exponents, self.zero(scalar))?;
+        self.store(statistics, targets, self.zero(scalar))?;
+
+        self.build_loop(state, count, &mut |i| {
+            let x = values(i)?.0;
+            let current = self.load(statistics, maximum)?.into_float_value();
+            let greater = self.builder.build_float_compare(FloatPredicate::OGT, x, current, "greater")?;
+
+            self.store(statistics, maximum, self.builder.build_select(greater, x, current, "maximum")?)
+        })?;
+
+        let maximum_value = self.load(statistics, maximum)?.into_float_value();
+
+        self.build_loop(state, count, &mut |i| {
+            let (x, y) = values(i)?;
+            let exp = self.exp(self.builder.build_float_sub(x, maximum_value, "")?)?;
+
+            let sum = self.builder.build_float_add(self.load(statistics, exponents)?.into_float_value(), exp, "")?;
+            self.store(statistics, exponents, sum.into())?;
+
+            let sum = self.builder.build_float_add(self.load(statistics, targets)?.into_float_value(), y, "")?;
+            self.store(statistics, targets, sum.into())
+        })
+    }
+
+    /// Emits a counted loop running `body` for every index in `0..count`
+    fn build_loop(&self, state: &State<'ctx>, count: u64, body: &mut dyn FnMut(IntValue<'ctx>) -> crate::Result<()>) -> crate::Result<()> {
+        // The body runs before the bound is tested, so empty ranges emit nothing
+        if count == 0 {
+            return Ok(());
+        }
+
+        let preheader = self.builder.get_insert_block()
+            .ok_or(crate::Error::from(Errors::CompilationFailed))?;
+        let header = self.context.append_basic_block(state.function, "loop");
+        let exit = self.context.append_basic_block(state.function, "loop_exit");
+
+        self.builder.build_unconditional_branch(header)?;
+        self.builder.position_at_end(header);
+
+        let index = self.builder.build_phi(self.context.i64_type(), "index")?;
+        index.add_incoming(&[(&self.index(0), preheader)]);
+
+        let current = index.as_basic_value().into_int_value();
+        body(current)?;
+
+        let next = self.builder.build_int_add(current, self.index(1), "next")?;
+        let latch = self.builder.get_insert_block()
+            .ok_or(crate::Error::from(Errors::CompilationFailed))?;
+        index.add_incoming(&[(&next, latch)]);
+
+        let condition = self.builder.build_int_compare(IntPredicate::ULT, next, self.index(count), "condition")?;
+        self.builder.build_conditional_branch(condition, header, exit)?;
+        self.builder.position_at_end(exit);
+
+        Ok(())
+    }
+
+    fn allocate(&self, layout: Layout, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        let pointer = self.builder.build_array_malloc(
+            self.scalar_type(layout.scalar),
+            self.index(layout.len()),
+            "buffer")?;
+
+        state.temporaries.push(pointer);
+
+        Ok(Buffer { pointer, layout })
+    }
+
+    /// `destination` if there is one, or a new temporary
+    fn output(&self, layout: Layout, destination: Option<PointerValue<'ctx>>, state: &mut State<'ctx>) -> crate::Result<Buffer<'ctx>> {
+        match destination {
+            Some(pointer) => Ok(Buffer { pointer, layout }),
+            None => self.allocate(layout, state),
+        }
+    }
+
+    fn load_pointer(&self, array: PointerValue<'ctx>, index: u64) -> crate::Result<PointerValue<'ctx>> {
+        let pointer_type = self.context.ptr_type(AddressSpace::default());
+        let slot = unsafe {
+            self.builder.build_in_bounds_gep(pointer_type, array, &[self.index(index)], "slot")?
+        };
+
+        Ok(self.builder.build_load(pointer_type, slot, "pointer")?.into_pointer_value())
+    }
+
+    fn load(&self, buffer: &Buffer<'ctx>, offset: IntValue<'ctx>) -> crate::Result<BasicValueEnum<'ctx>> {
+        let scalar_type = self.scalar_type(buffer.layout.scalar);
+        let pointer = unsafe {
+            self.builder.build_in_bounds_gep(scalar_type, buffer.pointer, &[offset], "element")?
+        };
+
+        Ok(self.builder.build_load(scalar_type, pointer, "value")?)
+    }
+
+    fn store(&self, buffer: &Buffer<'ctx>, offset: IntValue<'ctx>, value: BasicValueEnum<'ctx>) -> crate::Result<()> {
+        let scalar_type = self.scalar_type(buffer.layout.scalar);
+        let pointer = unsafe {
+            self.builder.build_in_bounds_gep(scalar_type, buffer.pointer, &[offset], "element")?
+        };
+
+        self.builder.build_store(pointer, value)?;
+        Ok(())
+    }
+
+    /// Maps an index into `output` onto the matching index into `operand`,
+    /// repeating elements over tensors, windowless values over windows and
+    /// single samples over samples
+    fn broadcast(&self, operand: &Layout, output: &Layout, index: IntValue<'ctx>) -> crate::Result<IntValue<'ctx>> {
+        let inner = self.builder.build_int_unsigned_rem(index, self.index(output.inner()), "inner")?;
+
+        let inner = if operand.element {
+            self.builder.build_int_unsigned_rem(inner, self.index(operand.channels), "inner")?
+        } else {
+            inner
+        };
+
+        if operand.blocks() > 1 {
+            let block = self.builder.build_int_unsigned_div(index, self.index(output.inner()), "block")?;
+            Ok(self.builder.build_int_add(self.block_base(operand, output, block)?, inner, "offset")?)
+        } else {
+            Ok(inner)
+        }
+    }
+
+    /// Offset of the window of `operand` read for `block` of `output`, a
+    /// window counted over every sample. Values without windows or samples
+    /// repeat their only one.
+    fn block_base(&self, operand: &Layout, output: &Layout, block: IntValue<'ctx>) -> crate::Result<IntValue<'ctx>> {
+        let mut window = self.index(0);
+
+        if operand.batch > 1 {
+            let sample = self.builder.build_int_unsigned_div(block, self.index(output.outer), "sample")?;
+            window = self.builder.build_int_mul(sample, self.index(operand.outer), "window")?;
+        }
+
+        if operand.outer > 1 {
+            let position = self.builder.build_int_unsigned_rem(block, self.index(output.outer), "position")?;
+            window = self.builder.build_int_add(window, position, "window")?;
+        }
+
+        Ok(self.builder.build_int_mul(window, self.index(operand.inner()), "block_base")?)
+    }
+
+    /// Offset of sample `sample` of a non-convergent `layout`, or of its only
+    /// sample
+    fn sample_base(&self, layout: &Layout, sample: IntValue<'ctx>) -> crate::Result<IntValue<'ctx>> {
+        if layout.batch > 1 {
+            Ok(self.builder.build_int_mul(sample, self.index(layout.inner()), "sample_base")?)
+        } else {
+            Ok(self.index(0))
+        }
+    }
+
+    fn element_offset(&self, layout: &Layout, x: IntValue<'ctx>, y: IntValue<'ctx>, z: IntValue<'ctx>,
+        channel: IntValue<'ctx>, base: IntValue<'ctx>) -> crate::Result<IntValue<'ctx>> {
+
+        let (width, height, _) = layout.shape;
+
+        let offset = self.builder.build_int_add(x,
+            self.builder.build_int_mul(y, self.index(width), "")?, "")?;
+        let offset = self.builder.build_int_add(offset,
+            self.builder.build_int_mul(z, self.index(width * height), "")?, "")?;
+        let offset = self.builder.build_int_mul(offset, self.index(layout.channels), "")?;
+        let offset = self.builder.build_int_add(offset, channel, "")?;
+
+        Ok(self.builder.build_int_add(base, offset, "offset")?)
+    }
+
+    fn arithmetic(&self, operation: Arithmetic, scalar: ScalarType, a: BasicValueEnum<'ctx>, b: BasicValueEnum<'ctx>) -> crate::Result<BasicValueEnum<'ctx>> {
+        if scalar.is_float() {
+            let (a, b) = (a.into_float_value(), b.into_float_value());
+
+            Ok(match operation {
+                Arithmetic::Add => self.builder.build_float_add(a, b, "add")?,
+                Arithmetic::Subtract => self.builder.build_float_sub(a, b, "sub")?,
+                Arithmetic::Multiply => self.builder.build_float_mul(a, b, "mul")?,
+                Arithmetic::Divide => self.builder.build_float_div(a, b, "div")?,
+            }.into())
+        } else {
+            let (a, b) = (a.into_int_value(), b.into_int_value());
+
+            Ok(match operation {
+                Arithmetic::Add => self.builder.build_int_add(a, b, "add")?,
+                Arithmetic::Subtract => self.builder.build_int_sub(a, b, "sub")?,
+                Arithmetic::Multiply => self.builder.build_int_mul(a, b, "mul")?,
+                Arithmetic::Divide => self.int_divide(scalar, a, b)?,
+            }.into())
+        }
+    }
+
+    /// Quotient of integers, saturating like the interpreter's conversion of
+    /// its float quotient: dividing by zero gives the maximum, the minimum or
+    /// zero by the sign of `a`, and `MIN / -1` gives the maximum. Both are
+    /// undefined in LLVM, so they divide by one instead.
+    fn int_divide(&self, scalar: ScalarType, a: IntValue<'ctx>, b: IntValue<'ctx>) -> crate::Result<IntValue<'ctx>> {
+        let ty = a.get_type();
+        let (zero, one) = (ty.const_zero(), ty.const_int(1, false));
+        let by_zero = self.builder.build_int_compare(IntPredicate::EQ, b, zero, "by_zero")?;
+
+        if !scalar.is_signed() {
+            let divisor = self.builder.build_select(by_zero, one, b, "divisor")?.into_int_value();
+            let quotient = self.builder.build_int_unsigned_div(a, divisor, "div")?;
+            let empty = self.builder.build_int_compare(IntPredicate::EQ, a, zero, "empty")?;
+            let infinite = self.builder.build_select(empty, zero, ty.const_all_ones(), "")?;
+
+            return Ok(self.builder.build_select(by_zero, infinite, quotient.into(), "div")?.into_int_value());
+        }
+
+        let minimum = ty.const_int(1 << (ty.get_bit_width() - 1), false);
+        let maximum = ty.const_int((1 << (ty.get_bit_width() - 1)) - 1, false);
+
+        let overflow = self.builder.build_and(
+            self.builder.build_int_compare(IntPredicate::EQ, a, minimum, "")?,
+            self.builder.build_int_compare(IntPredicate::EQ, b, ty.const_all_ones(), "")?, "overflow")?;
+        let guarded = self.builder.build_or(by_zero, overflow, "")?;
+        let divisor = self.builder.build_select(guarded, one, b, "divisor")?.into_int_value();
+        let quotient = self.builder.build_int_signed_div(a, divisor, "div")?;
+
+        let positive = self.builder.build_int_compare(IntPredicate::SGT, a, zero, "")?;
+        let negative = self.builder.build_int_compare(IntPredicate::SLT, a, zero, "")?;
+        let infinite = self.builder.build_select(positive, maximum,
+            self.builder.build_select(negative, minimum, zero, "")?.into_int_value(), "")?;
+        let quotient = self.builder.build_select(overflow, maximum, quotient, "")?;
+
+        Ok(self.builder.build_select(by_zero, infinite, quotient, "div")?.into_int_value())
+    }
+
+    fn sigmoid(&self, x: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        // 1 / (1 + e^-x)
+        let one = x.get_type().const_float(1.0);
+        let exp = self.exp(self.builder.build_float_neg(x, "neg")?)?;
+
+        Ok(self.builder.build_float_div(one, self.builder.build_float_add(one, exp, "")?, "sigmoid")?)
+    }
+
+    fn tanh(&self, x: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        // 1 - 2 / (e^2x + 1)
+        let one = x.get_type().const_float(1.0);
+        let two = x.get_type().const_float(2.0);
+        let exp = self.exp(self.builder.build_float_mul(two, x, "")?)?;
+        let fraction = self.builder.build_float_div(two, self.builder.build_float_add(exp, one, "")?, "")?;
+
+        Ok(self.builder.build_float_sub(one, fraction, "tanh")?)
+    }
+
+    fn relu(&self, x: FloatValue<'ctx>, beta: f64) -> crate::Result<FloatValue<'ctx>> {
+        let zero = x.get_type().const_float(0.0);
+        let positive = self.builder.build_float_compare(FloatPredicate::OGT, x, zero, "positive")?;
+        let negative = if beta == 0.0 {
+            zero
+        } else {
+            self.builder.build_float_mul(x, x.get_type().const_float(beta), "")?
+        };
+
+        Ok(self.builder.build_select(positive, x, negative, "relu")?.into_float_value())
+    }
+
+    fn elu(&self, x: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        let zero = x.get_type().const_float(0.0);
+        let one = x.get_type().const_float(1.0);
+        let positive = self.builder.build_float_compare(FloatPredicate::OGT, x, zero, "positive")?;
+        let negative = self.builder.build_float_sub(self.exp(x)?, one, "")?;
+
+        Ok(self.builder.build_select(positive, x, negative, "elu")?.into_float_value())
+    }
+
+    fn swish(&self, x: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        Ok(self.builder.build_float_mul(x, self.sigmoid(x)?, "swish")?)
+    }
+
+    fn softplus(&self, x: FloatValue<'ctx>, beta: f64) -> crate::Result<FloatValue<'ctx>> {
+        // ln(1 + e^(beta * x)) / beta
+        let one = x.get_type().const_float(1.0);
+        let beta = x.get_type().const_float(beta);
+        let exp = self.exp(self.builder.build_float_mul(beta, x, "")?)?;
+        let log = self.log(self.builder.build_float_add(one, exp, "")?)?;
+
+        Ok(self.builder.build_float_div(log, beta, "softplus")?)
+    }
+
+    fn activation(&self, activation_fn: &crate::ActivationFunction, x: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        match activation_fn {
+            crate::ActivationFunction::Sigmoid => self.sigmoid(x),
+            crate::ActivationFunction::Tanh => self.tanh(x),
+            crate::ActivationFunction::Relu => self.relu(x, 0.0),
+            crate::ActivationFunction::LeakyRelu(beta) => self.relu(x, *beta as f64),
+            crate::ActivationFunction::Elu => self.elu(x),
+            crate::ActivationFunction::Swish => self.swish(x),
+            crate::ActivationFunction::Softplus(beta) => self.softplus(x, *beta as f64),
+        }
+    }
+
+    fn derivative(&self, activation_fn: &crate::ActivationFunction, x: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        let zero = x.get_type().const_float(0.0);
+        let one = x.get_type().const_float(1.0);
+
+        match activation_fn {
+            crate::ActivationFunction::Sigmoid => {
+                // s * (1 - s)
+                let s = self.sigmoid(x)?;
+                Ok(self.builder.build_float_mul(s, self.builder.build_float_sub(one, s, "")?, "sigmoid'")?)
+            },
+            crate::ActivationFunction::Tanh => {
+                // 1 - t^2
+                let t = self.tanh(x)?;
+                Ok(self.builder.build_float_sub(one, self.builder.build_float_mul(t, t, "")?, "tanh'")?)
+            },
+            crate::ActivationFunction::Relu | crate::ActivationFunction::LeakyRelu(_) | crate::ActivationFunction::Elu => {
+                let positive = self.builder.build_float_compare(FloatPredicate::OGT, x, zero, "positive")?;
+                let negative = match activation_fn {
+                    crate::ActivationFunction::LeakyRelu(beta) => x.get_type().const_float(*beta as f64),
+                    crate::ActivationFunction::Elu => self.exp(x)?,
+                    _ => zero,
+                };
+
+                Ok(self.builder.build_select(positive, one, negative, "relu'")?.into_float_value())
+            },
+            crate::ActivationFunction::Swish => {
+                // s * (1 + x * (1 - s))
+                let s = self.sigmoid(x)?;
+                let complement = self.builder.build_float_sub(one, s, "")?;
+                let inner = self.builder.build_float_add(one, self.builder.build_float_mul(x, complement, "")?, "")?;
+
+                Ok(self.builder.build_float_mul(s, inner, "swish'")?)
+            },
+            crate::ActivationFunction::Softplus(beta) => {
+                self.sigmoid(self.builder.build_float_mul(x, x.get_type().const_float(*beta as f64), "")?)
+            },
+        }
+    }
+
+    /// Loss of a single prediction `x` against its target `y`, before
+    /// taking the mean
+    fn loss(&self, loss: &crate::Loss, x: FloatValue<'ctx>, y: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        let one = x.get_type().const_float(1.0);
+        let difference = self.builder.build_float_sub(x, y, "difference")?;
+
+        match loss {
+            crate::Loss::MeanSquaredError => Ok(self.builder.build_float_mul(difference, difference, "squared")?),
+            crate::Loss::MeanAbsoluteError => self.call_intrinsic("llvm.fabs", difference),
+            crate::Loss::BinaryCrossEntropy => {
+                // -(y * ln(p) + (1 - y) * ln(1 - p))
+                let p = self.clamp_probability(x)?;
+                let positive = self.builder.build_float_mul(y, self.log(p)?, "")?;
+                let negative = self.builder.build_float_mul(
+                    self.builder.build_float_sub(one, y, "")?,
+                    self.log(self.builder.build_float_sub(one, p, "")?)?, "")?;
+
+                Ok(self.builder.build_float_neg(self.builder.build_float_add(positive, negative, "")?, "bce")?)
+            },
+            crate::Loss::Huber(delta) => {
+                // d^2 / 2 within delta, delta * (|d| - delta / 2) beyond
+                let delta = x.get_type().const_float(*delta as f64);
+                let half = x.get_type().const_float(0.5);
+                let absolute = self.call_intrinsic("llvm.fabs", difference)?;
+                let within = self.builder.build_float_compare(FloatPredicate::OLE, absolute, delta, "within")?;
+
+                let quadratic = self.builder.build_float_mul(half,
+                    self.builder.build_float_mul(difference, difference, "")?, "quadratic")?;
+                let linear = self.builder.build_float_mul(delta,
+                    self.builder.build_float_sub(absolute, self.builder.build_float_mul(half, delta, "")?, "")?, "linear")?;
+
+                Ok(self.builder.build_select(within, quadratic, linear, "huber")?.into_float_value())
+            },
+            crate::Loss::CategoricalCrossEntropy => Errors::UnsupportedOperand.into(),
+        }
+    }
+
+    /// Derivative of `loss` with respect to a single prediction `x`, before
+    /// dividing by the number of predictions
+    fn loss_derivative(&self, loss: &crate::Loss, x: FloatValue<'ctx>, y: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        let one = x.get_type().const_float(1.0);
+        let difference = self.builder.build_float_sub(x, y, "difference")?;
+
+        match loss {
+            crate::Loss::MeanSquaredError => Ok(self.builder.build_float_mul(x.get_type().const_float(2.0), difference, "mse'")?),
+            crate::Loss::MeanAbsoluteError => self.sign(difference),
+            crate::Loss::BinaryCrossEntropy => {
+                // (p - y) / (p * (1 - p))
+                let p = self.clamp_probability(x)?;
+                let denominator = self.builder.build_float_mul(p, self.builder.build_float_sub(one, p, "")?, "")?;
+
+                Ok(self.builder.build_float_div(self.builder.build_float_sub(p, y, "")?, denominator, "bce'")?)
+            },
+            crate::Loss::Huber(delta) => {
+                let delta = x.get_type().const_float(*delta as f64);
+                let absolute = self.call_intrinsic("llvm.fabs", difference)?;
+                let within = self.builder.build_float_compare(FloatPredicate::OLE, absolute, delta, "within")?;
+                let linear = self.builder.build_float_mul(delta, self.sign(difference)?, "")?;
+
+                Ok(self.builder.build_select(within, difference, linear, "huber'")?.into_float_value())
+            },
+            crate::Loss::CategoricalCrossEntropy => Errors::UnsupportedOperand.into(),
+        }
+    }
+
+    /// Keeps a probability away from 0 and 1 so its logarithms stay finite
+    fn clamp_probability(&self, p: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        let epsilon = p.get_type().const_float(1e-7);
+        let ceiling = p.get_type().const_float(1.0 - 1e-7);
+
+        let below = self.builder.build_float_compare(FloatPredicate::OLT, p, epsilon, "below")?;
+        let p = self.builder.build_select(below, epsilon, p, "clamped")?.into_float_value();
+        let above = self.builder.build_float_compare(FloatPredicate::OGT, p, ceiling, "above")?;
+
+        Ok(self.builder.build_select(above, ceiling, p, "clamped")?.into_float_value())
+    }
+
+    fn sign(&self, x: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        let zero = x.get_type().const_float(0.0);
+        let positive = self.builder.build_float_compare(FloatPredicate::OGT, x, zero, "positive")?;
+        let negative = self.builder.build_float_compare(FloatPredicate::OLT, x, zero, "negative")?;
+
+        let value = self.builder.build_select(negative, x.get_type().const_float(-1.0), zero, "")?.into_float_value();
+        Ok(self.builder.build_select(positive, x.get_type().const_float(1.0), value, "sign")?.into_float_value())
+    }
+
+    fn exp(&self, x: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        self.call_intrinsic("llvm.exp", x)
+    }
+
+    fn log(&self, x: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        self.call_intrinsic("llvm.log", x)
+    }
+
+    fn call_intrinsic(&self, name: &str, x: FloatValue<'ctx>) -> crate::Result<FloatValue<'ctx>> {
+        let function = Intrinsic::find(name)
+            .and_then(|intrinsic| intrinsic.get_declaration(&self.module, &[x.get_type().into()]))
+            .ok_or(crate::Error::from(Errors::CompilationFailed))?;
+
+        self.builder.build_call(function, &[x.into()], name)?
+            .try_as_basic_value()
+            .left()
+            .map(|value| value.into_float_value())
+            .ok_or(Errors::CompilationFailed.into())
+    }
+
+    fn scalar_type(&self, scalar: ScalarType) -> BasicTypeEnum<'ctx> {
+        match scalar {
+            ScalarType::F32 => self.context.f32_type().into(),
+            ScalarType::F64 => self.context.f64_type().into(),
+            ScalarType::U8 | ScalarType::I8 => self.context.i8_type().into(),
+            ScalarType::U16 | ScalarType::I16 => self.context.i16_type().into(),
+            ScalarType::U32 | ScalarType::I32 => self.context.i32_type().into(),
+            ScalarType::U64 | ScalarType::I64 => self.context.i64_type().into(),
+        }
+    }
+
+    fn zero(&self, scalar: ScalarType) -> BasicValueEnum<'ctx> {
+        match self.scalar_type(scalar) {
+            BasicTypeEnum::FloatType(ty) => ty.const_float(0.0).into(),
+            BasicTypeEnum::IntType(ty) => ty.const_int(0, false).into(),
+            ty => ty.const_zero(),
+        }
+    }
+
+    fn index(&self, value: u64) -> IntValue<'ctx> {
+        self.context.i64_type().const_int(value, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use inkwell::context::Context;
+    use inkwell::values::FunctionValue;
+
+    use super::Compiler;
+    use crate::{ ActivationFunction, Dimension, Element, Engine, Graph, Kernel, Layer, Tensor, Value };
+    use crate::layers::Dense;
+
+    /// Number of loops emitted into `function`, counted as the blocks
+    /// branched to from themselves or from a block after them
+    fn loops(function: FunctionValue) -> usize {
+        let blocks = function.get_basic_blocks();
+
+        blocks.iter()
+            .enumerate()
+            .filter(|(position, block)| {
+                let mut next = block.get_first_use();
+
+                while let Some(current) = next {
+                    let predecessor = current.get_user().into_instruction_value().get_parent();
+
+                    if predecessor.and_then(|predecessor| blocks.iter().position(|block| *block == predecessor))
+                        .is_some_and(|predecessor| predecessor >= *position) {
+                        return true;
+                    }
+
+                    next = current.get_next_use();
+                }
+
+                false
+            })
+            .count()
+    }
+
+    #[test]
+    fn empty_tensor_emits_no_loop() {
+        let context = Context::create();
+        let compiler = Compiler::new(&context, "test");
+
+        let mut graph = Graph::new();
+        let x = graph.tensor::<f64>(Dimension::new(0, 3, 1), 2);
+        let y = x.clone().add(x).unwrap().relu().unwrap();
+
+        let function = compiler.compile("kernel", &[&y]).unwrap();
+        assert_eq!(loops(function), 0);
+    }
+
+    #[test]
+    fn empty_tensor_runs() {
+        let engine = Engine::new().unwrap();
+
+        let mut graph = Graph::new();
+        let x = graph.tensor::<f64>(Dimension::new(0, 3, 1), 2);
+        let y = x.clone().add(x).unwrap().relu().unwrap();
+
+        let kernel: Kernel<Tensor<f64>, Tensor<f64>, Tensor<f64>> = engine.compile(&y).unwrap();
+        let input = Tensor::zeros(Dimension::new(0, 3, 1), 2).unwrap();
+        let unused = Tensor::zeros(Dimension::new(1, 1, 1), 1).unwrap();
+
+        let output = kernel.run(&input, &unused).unwrap();
+        assert_eq!(output.dimension(), &Dimension::new(0, 3, 1));
+        assert!(output.is_empty());
+    }
+
+    /// Quotients of `dividend` by `divisors` computed by a kernel and by the
+    /// interpreter
+    fn quotients<F: crate::Scalar>(dividend: F, divisors: Vec<F>) -> (Tensor<F>, Tensor<F>) {
+        let engine = Engine::new().unwrap();
+        let dimension = Dimension::new(divisors.len() as u32, 1, 1);
+
+        let mut graph = Graph::new();
+        let a = graph.element::<F>(1);
+        let b = graph.tensor::<F>(dimension, 1);
+        let quotient = a.divide(b).unwrap();
+
+        let compiled: Kernel<Element<F>, Tensor<F>, Tensor<F>> = engine.compile(&quotient).unwrap();
+        let interpreted: Kernel<Element<F>, Tensor<F>, Tensor<F>> = engine.interpret(&quotient).unwrap();
+        let (a, b) = (Element::from_slice(&[dividend]).unwrap(), Tensor::from_vec(divisors, dimension, 1).unwrap());
+
+        (compiled.run(&a, &b).unwrap(), interpreted.run(&a, &b).unwrap())
+    }
+
+    #[test]
+    fn integer_division_saturates() {
+        let cases = [
+            (7i32, vec![2, -2, 0], vec![3, -3, i32::MAX]),
+            (-9, vec![0, 4], vec![i32::MIN, -2]),
+            (0, vec![0], vec![0]),
+            (i32::MIN, vec![-1, 1], vec![i32::MAX, i32::MIN]),
+        ];
+
+        for (dividend, divisors, expected) in cases {
+            let (compiled, interpreted) = quotients(dividend, divisors);
+            assert_eq!(compiled.as_slice(), expected.as_slice());
+            assert_eq!(compiled.as_slice(), interpreted.as_slice());
+        }
+
+        for (dividend, expected) in [(7u8, u8::MAX), (0, 0)] {
+            let (compiled, interpreted) = quotients(dividend, vec![0]);
+            assert_eq!(compiled.as_slice(), &[expected]);
+            assert_eq!(compiled.as_slice(), interpreted.as_slice());
+        }
+    }
+
+    /// Input, weights and biases of a `Dense` layer of 3 inputs and 4 outputs
+    fn dense_parameters() -> (Value, Value, Value) {
+        let mut graph = Graph::new();
+
+        (graph.tensor::<f32>(Dimension::new(1, 3, 1), 1),
+            graph.tensor::<f32>(Dimension::new(3, 4, 1), 1),
+            graph.tensor::<f32>(Dimension::new(1, 4, 1), 1))
+    }
+
+    #[test]
+    fn dense_tail_fuses_into_one_loop() {
+        let context = Context::create();
+        let compiler = Compiler::new(&context, "test");
+
+        // Two nested loops for the product, written straight into the output
+        let (input, weights, _) = dense_parameters();
+        let product = input.multiply(weights).unwrap();
+        let function = compiler.compile("product", &[&product]).unwrap();
+        assert_eq!(loops(function), 2);
+
+        // The bias and the activation only add the loop evaluating both
+        let (input, weights, biases) = dense_parameters();
+        let dense = Dense::<f32>::new(3, 4, Some(ActivationFunction::Tanh))
+            .operations(input, (weights, biases))
+            .unwrap();
+        let function = compiler.compile("dense", &[&dense]).unwrap();
+        assert_eq!(loops(function), 3);
+    }
+}
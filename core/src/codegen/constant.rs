@@ -1,3 +1,7 @@
+use super::{ GeneralType, ElementType, ScalarType };
+use crate::Errors;
+
+#[derive(Clone)]
 #[derive(PartialEq)]
 pub enum Constant {
     ScalarF32(f32),
@@ -33,3 +37,93 @@ pub enum Constant {
     TensorI32(crate::Tensor<i32>),
     TensorI64(crate::Tensor<i64>),
 }
+
+impl Constant {
+    /// Every scalar of the constant in storage order, tensors being
+    /// interleaved
+    pub fn scalars(&self) -> Vec<f64> {
+        match self {
+            Constant::ScalarF32(value) => vec![*value as f64],
+            Constant::ScalarF64(value) => vec![*value],
+            Constant::ScalarU8(value) => vec![*value as f64],
+            Constant::ScalarU16(value) => vec![*value as f64],
+            Constant::ScalarU32(value) => vec![*value as f64],
+            Constant::ScalarU64(value) => vec![*value as f64],
+            Constant::ScalarI8(value) => vec![*value as f64],
+            Constant::ScalarI16(value) => vec![*value as f64],
+            Constant::ScalarI32(value) => vec![*value as f64],
+            Constant::ScalarI64(value) => vec![*value as f64],
+            Constant::ElementF32(element) => element.iter().map(|x| *x as f64).collect(),
+            Constant::ElementF64(element) => element.to_vec(),
+            Constant::ElementU8(element) => element.iter().map(|x| *x as f64).collect(),
+            Constant::ElementU16(element) => element.iter().map(|x| *x as f64).collect(),
+            Constant::ElementU32(element) => element.iter().map(|x| *x as f64).collect(),
+            Constant::ElementU64(element) => element.iter().map(|x| *x as f64).collect(),
+            Constant::ElementI8(element) => element.iter().map(|x| *x as f64).collect(),
+            Constant::ElementI16(element) => element.iter().map(|x| *x as f64).collect(),
+            Constant::ElementI32(element) => element.iter().map(|x| *x as f64).collect(),
+            Constant::ElementI64(element) => element.iter().map(|x| *x as f64).collect(),
+            Constant::TensorF32(tensor) => tensor.as_slice().iter().map(|x| *x as f64).collect(),
+            Constant::TensorF64(tensor) => tensor.as_slice().to_vec(),
+            Constant::TensorU8(tensor) => tensor.as_slice().iter().map(|x| *x as f64).collect(),
+            Constant::TensorU16(tensor) => tensor.as_slice().iter().map(|x| *x as f64).collect(),
+            Constant::TensorU32(tensor) => tensor.as_slice().iter().map(|x| *x as f64).collect(),
+            Constant::TensorU64(tensor) => tensor.as_slice().iter().map(|x| *x as f64).collect(),
+            Constant::TensorI8(tensor) => tensor.as_slice().iter().map(|x| *x as f64).collect(),
+            Constant::TensorI16(tensor) => tensor.as_slice().iter().map(|x| *x as f64).collect(),
+            Constant::TensorI32(tensor) => tensor.as_slice().iter().map(|x| *x as f64).collect(),
+            Constant::TensorI64(tensor) => tensor.as_slice().iter().map(|x| *x as f64).collect(),
+        }
+    }
+
+    /// Constant of `general_type` holding `data` in storage order, converted
+    /// with `as` semantics
+    pub fn from_scalars(general_type: GeneralType, data: &[f64]) -> crate::Result<Self> {
+        if data.len() != general_type.scalars() {
+            return Errors::IncompatibleOperandDimensions.into();
+        }
+
+        Ok(match general_type {
+            GeneralType::Element(ElementType(1, scalar)) => match scalar {
+                ScalarType::F32 => Constant::ScalarF32(data[0] as f32),
+                ScalarType::F64 => Constant::ScalarF64(data[0]),
+                ScalarType::U8 => Constant::ScalarU8(data[0] as u8),
+                ScalarType::U16 => Constant::ScalarU16(data[0] as u16),
+                ScalarType::U32 => Constant::ScalarU32(data[0] as u32),
+                ScalarType::U64 => Constant::ScalarU64(data[0] as u64),
+                ScalarType::I8 => Constant::ScalarI8(data[0] as i8),
+                ScalarType::I16 => Constant::ScalarI16(data[0] as i16),
+                ScalarType::I32 => Constant::ScalarI32(data[0] as i32),
+                ScalarType::I64 => Constant::ScalarI64(data[0] as i64),
+            },
+            GeneralType::Element(ElementType(_, scalar)) => match scalar {
+                ScalarType::F32 => Constant::ElementF32(crate::Element::from_slice(&convert::<f32>(data))?),
+                ScalarType::F64 => Constant::ElementF64(crate::Element::from_slice(&convert::<f64>(data))?),
+                ScalarType::U8 => Constant::ElementU8(crate::Element::from_slice(&convert::<u8>(data))?),
+                ScalarType::U16 => Constant::ElementU16(crate::Element::from_slice(&convert::<u16>(data))?),
+                ScalarType::U32 => Constant::ElementU32(crate::Element::from_slice(&convert::<u32>(data))?),
+                ScalarType::U64 => Constant::ElementU64(crate::Element::from_slice(&convert::<u64>(data))?),
+                ScalarType::I8 => Constant::ElementI8(crate::Element::from_slice(&convert::<i8>(data))?),
+                ScalarType::I16 => Constant::ElementI16(crate::Element::from_slice(&convert::<i16>(data))?),
+                ScalarType::I32 => Constant::ElementI32(crate::Element::from_slice(&convert::<i32>(data))?),
+                ScalarType::I64 => Constant::ElementI64(crate::Element::from_slice(&convert::<i64>(data))?),
+            },
+            GeneralType::Tensor(dimension, ElementType(channels, scalar)) => match scalar {
+                ScalarType::F32 => Constant::TensorF32(crate::Tensor::from_vec(convert::<f32>(data), dimension, channels as usize)?),
+                ScalarType::F64 => Constant::TensorF64(crate::Tensor::from_vec(convert::<f64>(data), dimension, channels as usize)?),
+                ScalarType::U8 => Constant::TensorU8(crate::Tensor::from_vec(convert::<u8>(data), dimension, channels as usize)?),
+                ScalarType::U16 => Constant::TensorU16(crate::Tensor::from_vec(convert::<u16>(data), dimension, channels as usize)?),
+                ScalarType::U32 => Constant::TensorU32(crate::Tensor::from_vec(convert::<u32>(data), dimension, channels as usize)?),
+                ScalarType::U64 => Constant::TensorU64(crate::Tensor::from_vec(convert::<u64>(data), dimension, channels as usize)?),
+                ScalarType::I8 => Constant::TensorI8(crate::Tensor::from_vec(convert::<i8>(data), dimension, channels as usize)?),
+                ScalarType::I16 => Constant::TensorI16(crate::Tensor::from_vec(convert::<i16>(data), dimension, channels as usize)?),
+                ScalarType::I32 => Constant::TensorI32(crate::Tensor::from_vec(convert::<i32>(data), dimension, channels as usize)?),
+                ScalarType::I64 => Constant::TensorI64(crate::Tensor::from_vec(convert::<i64>(data), dimension, channels as usize)?),
+            },
+        })
+    }
+}
+
+fn convert<F: crate::Scalar>(data: &[f64]) -> Vec<F> {
+    data.iter().map(|value| F::from_f64(*value)).collect()
+}
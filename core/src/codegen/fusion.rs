@@ -0,0 +1,86 @@
+use std::collections::{ HashMap, HashSet };
+
+use super::{ Value, Operand, Node, GeneralType };
+
+/// Element-wise nodes evaluated scalar by scalar within the loop of the one
+/// element-wise node reading them, instead of into a buffer of their own, so
+/// that a chain of them takes a single loop and keeps its intermediates in
+/// registers
+pub(super) struct Fusion {
+    inlined: HashSet<usize>,
+}
+
+impl Fusion {
+    pub fn plan(outputs: &[&Value]) -> Self {
+        let order = Value::postorder(outputs);
+
+        // Outputs are written out whatever reads them
+        let mut uses: HashMap<usize, usize> = HashMap::new();
+
+        for value in outputs.iter().copied().chain(order.iter().flat_map(operands)) {
+            if let Some(id) = value.id() {
+                *uses.entry(id).or_default() += 1;
+            }
+        }
+
+        let mut inlined = HashSet::new();
+
+        for value in order.iter().filter(|value| is_elementwise(value)) {
+            for operand in operands(value) {
+                // Operands read more than once, or at other positions than
+                // their own, would be evaluated again for every read
+                let fusable = is_elementwise(operand)
+                    && operand.general_type == value.general_type
+                    && operand.batch == value.batch
+                    && operand.convergent == value.convergent;
+
+                if let Some(id) = operand.id().filter(|id| fusable && uses.get(id) == Some(&1)) {
+                    inlined.insert(id);
+                }
+            }
+        }
+
+        Self { inlined }
+    }
+
+    /// Whether `value` is evaluated within the loop of the node reading it
+    pub fn is_inlined(&self, value: &Value) -> bool {
+        value.id().is_some_and(|id| self.inlined.contains(&id))
+    }
+}
+
+fn operands(value: &Value) -> Vec<&Value> {
+    match &value.inner {
+        Operand::Node(node) => node.operands(),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether every scalar of `value` only depends on the scalars of its
+/// operands at the same position, broadcast as needed
+fn is_elementwise(value: &Value) -> bool {
+    let node = match &value.inner {
+        Operand::Node(node) => node,
+        _ => return false,
+    };
+
+    match node.as_ref() {
+        Node::Multiply(a, b) => !matches!(
+            (a.general_type, b.general_type),
+            (GeneralType::Tensor(..), GeneralType::Tensor(..))
+        ),
+        Node::Add(..)
+        | Node::Subtract(..)
+        | Node::Divide(..)
+        | Node::HadamardProduct(..)
+        | Node::Sigmoid(_)
+        | Node::Tanh(_)
+        | Node::Relu(_)
+        | Node::LeakyRelu(..)
+        | Node::Elu(_)
+        | Node::Swish(_)
+        | Node::Softplus(..)
+        | Node::Derivative(..) => true,
+        _ => false,
+    }
+}
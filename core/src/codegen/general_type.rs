@@ -1,6 +1,51 @@
 #[derive(PartialEq, Eq)]
 #[derive(Copy, Clone)]
 pub enum GeneralType {
-    Tensor(u32, u32, u32, super::ElementType),
+    Tensor(crate::Dimension, super::ElementType),
     Element(super::ElementType)
-}
\ No newline at end of file
+}
+
+impl GeneralType {
+    /// Number of scalars a buffer of this type holds
+    pub fn scalars(&self) -> usize {
+        match self {
+            GeneralType::Tensor(dimension, element) => dimension.elements() * element.0 as usize,
+            GeneralType::Element(element) => element.0 as usize,
+        }
+    }
+
+    pub fn scalar_type(&self) -> super::ScalarType {
+        match self {
+            GeneralType::Tensor(_, element) | GeneralType::Element(element) => element.1,
+        }
+    }
+
+    pub fn element_type(&self) -> super::ElementType {
+        match self {
+            GeneralType::Tensor(_, element) | GeneralType::Element(element) => *element,
+        }
+    }
+
+    /// Dimension of a tensor, a single element for elements
+    pub fn dimension(&self) -> crate::Dimension {
+        match self {
+            GeneralType::Tensor(dimension, _) => *dimension,
+            GeneralType::Element(_) => crate::Dimension::new(1, 1, 1),
+        }
+    }
+
+    /// Type of a buffer holding `batch` samples of this type one after the
+    /// other, as a tensor with one more axis counting them. A single sample
+    /// keeps its own type.
+    pub fn batched(&self, batch: u32) -> crate::Result<GeneralType> {
+        match self {
+            _ if batch == 1 => Ok(*self),
+            GeneralType::Tensor(dimension, element) => {
+                Ok(GeneralType::Tensor(dimension.insert_axis(dimension.rank(), batch)?, *element))
+            },
+            GeneralType::Element(element) => {
+                Ok(GeneralType::Tensor(crate::Dimension::from_axes(&[batch])?, *element))
+            },
+        }
+    }
+}
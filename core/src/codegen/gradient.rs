@@ -0,0 +1,539 @@
+use std::collections::HashMap;
+
+use super::{
+    Value,
+    Operand,
+    Node,
+    GeneralType,
+    ElementType,
+};
+
+use super::value::Convergent;
+use crate::Errors;
+
+/// Accumulates the gradients flowing into the parameters being
+/// differentiated against while walking the graph from its output
+struct Backward<'a> {
+    indices: &'a [u32],
+    gradients: Vec<Option<Value>>,
+    /// Whether each node reads any of the parameters, by id
+    depends: HashMap<usize, bool>,
+    /// Sum of the gradients each node has received from its users so far
+    pending: HashMap<usize, Value>,
+}
+
+impl Value {
+    /// Builds the gradient of the sum of every scalar of this value with
+    /// respect to each of the parameters in `wrt`
+    pub fn gradient(&self, wrt: &[&Value]) -> crate::Result<Vec<Value>> {
+        let mut indices = Vec::with_capacity(wrt.len());
+
+        for parameter in wrt {
+            match parameter.inner {
+                Operand::Parameter(index, _) => indices.push(index),
+                _ => {
+                    return Errors::RequiresParameter.into();
+                }
+            }
+        }
+
+        let mut backward = Backward {
+            indices: &indices,
+            gradients: vec![None; wrt.len()],
+            depends: HashMap::new(),
+            pending: HashMap::new(),
+        };
+
+        // Operands come before the nodes reading them
+        let order = Value::postorder(&[self]);
+
+        for value in &order {
+            if let (Operand::Node(node), Some(id)) = (&value.inner, value.id()) {
+                let depends = node.operands().iter().any(|operand| backward.depends(operand));
+                backward.depends.insert(id, depends);
+            }
+        }
+
+        let seed = constant_like(self.general_type, 1.0)?
+            .broadcast(self.general_type, self.batch, self.convergent)?;
+
+        backward.send(self, seed)?;
+
+        // Every user of a node is done with it by the time it comes up
+        for value in order.iter().rev() {
+            if let Some(upstream) = value.id().and_then(|id| backward.pending.remove(&id)) {
+                backward.propagate(value, upstream)?;
+            }
+        }
+
+        backward.gradients.into_iter()
+            .zip(wrt.iter())
+            .map(|(gradient, parameter)| match gradient {
+                Some(gradient) => Ok(gradient),
+                None => constant_like(parameter.general_type, 0.0)?
+                    .broadcast(parameter.general_type, parameter.batch, Convergent::None),
+            })
+            .collect()
+    }
+}
+
+impl<'a> Backward<'a> {
+    fn depends(&self, value: &Value) -> bool {
+        match &value.inner {
+            Operand::Parameter(index, _) => self.indices.contains(index),
+            Operand::Constant(_) => false,
+            Operand::Node(_) => value.id()
+                .and_then(|id| self.depends.get(&id).copied())
+                .unwrap_or(false),
+        }
+    }
+
+    /// Hands `upstream`, one of the gradients of the output with respect to
+    /// `value`, to `value`. Parameters add it to their gradient, nodes hold
+    /// on to it until every user of theirs has sent its own.
+    fn send(&mut self, value: &Value, upstream: Value) -> crate::Result<()> {
+        if !self.depends(value) {
+            return Ok(());
+        }
+
+        // Windowless operands receive the sum of the gradients of all windows,
+        // and operands shared by every sample that of all samples
+        let upstream = if (upstream.convergent != Convergent::None && value.convergent == Convergent::None)
+            || upstream.batch != value.batch {
+            upstream.accumulate(value.batch, value.convergent)
+        } else {
+            upstream
+        };
+
+        if let Operand::Parameter(index, _) = value.inner {
+            for (i, wrt) in self.indices.iter().enumerate() {
+                if *wrt == index {
+                    self.gradients[i] = Some(match self.gradients[i].take() {
+                        Some(gradient) => gradient.add(upstream.clone())?,
+                        None => upstream.clone(),
+                    });
+                }
+            }
+        } else if let Some(id) = value.id() {
+            let gradient = match self.pending.remove(&id) {
+                Some(gradient) => gradient.add(upstream)?,
+                None => upstream,
+            };
+
+            self.pending.insert(id, gradient);
+        }
+
+        Ok(())
+    }
+
+    /// Hands `upstream`, the whole gradient of the output with respect to
+    /// `value`, down to the operands of `value`
+    fn propagate(&mut self, value: &Value, upstream: Value) -> crate::Result<()> {
+        let node = match &value.inner {
+            Operand::Node(node) => node,
+            _ => return Ok(()),
+        };
+
+        match node.as_ref() {
+            Node::Add(a, b) => {
+                if self.depends(a) {
+                    self.send(a, upstream.clone())?;
+                }
+
+                if self.depends(b) {
+                    self.send(b, upstream)?;
+                }
+            },
+            Node::Subtract(a, b) => {
+                if self.depends(a) {
+                    self.send(a, upstream.clone())?;
+                }
+
+                if self.depends(b) {
+                    self.send(b, negate(upstream)?)?;
+                }
+            },
+            Node::HadamardProduct(a, b) => {
+                if self.depends(a) {
+                    self.send(a, upstream.clone().hadamard_product(b.clone())?)?;
+                }
+
+                if self.depends(b) {
+                    self.send(b, upstream.hadamard_product(a.clone())?)?;
+                }
+            },
+            Node::Multiply(a, b) => match (a.general_type, b.general_type) {
+                (GeneralType::Tensor(..), GeneralType::Tensor(..)) => {
+                    if self.depends(a) {
+                        self.send(a, upstream.clone().multiply(b.clone().transpose()?)?)?;
+                    }
+
+                    if self.depends(b) {
+                        self.send(b, a.clone().transpose()?.multiply(upstream)?)?;
+                    }
+                },
+                (GeneralType::Tensor(..), GeneralType::Element(_)) => {
+                    if self.depends(a) {
+                        self.send(a, upstream.clone().multiply(b.clone())?)?;
+                    }
+
+                    if self.depends(b) {
+                        self.send(b, upstream.hadamard_product(a.clone())?.sum()?)?;
+                    }
+                },
+                (GeneralType::Element(_), GeneralType::Tensor(..)) => {
+                    if self.depends(a) {
+                        self.send(a, upstream.clone().hadamard_product(b.clone())?.sum()?)?;
+                    }
+
+                    if self.depends(b) {
+                        self.send(b, a.clone().multiply(upstream)?)?;
+                    }
+                },
+                (GeneralType::Element(_), GeneralType::Element(_)) => {
+                    if self.depends(a) {
+                        self.send(a, upstream.clone().multiply(b.clone())?)?;
+                    }
+
+                    if self.depends(b) {
+                        self.send(b, upstream.multiply(a.clone())?)?;
+                    }
+                },
+            },
+            Node::Divide(a, b) => match (a.general_type, b.general_type) {
+                (GeneralType::Element(_), GeneralType::Tensor(..)) => {
+                    let reciprocal = constant_like(a.general_type, 1.0)?.divide(b.clone())?;
+
+                    if self.depends(a) {
+                        self.send(a, upstream.clone().hadamard_product(reciprocal.clone())?.sum()?)?;
+                    }
+
+                    // d(a / b)/db = -(a / b) / b
+                    if self.depends(b) {
+                        self.send(b, negate(upstream
+                            .hadamard_product(value.clone())?
+                            .hadamard_product(reciprocal)?)?)?;
+                    }
+                },
+                _ => {
+                    if self.depends(a) {
+                        self.send(a, upstream.clone().divide(b.clone())?)?;
+                    }
+
+                    // d(a / b)/db = -a / b^2
+                    if self.depends(b) {
+                        let numerator = match (upstream.general_type, b.general_type) {
+                            (GeneralType::Tensor(..), GeneralType::Element(_)) => upstream.hadamard_product(a.clone())?.sum()?,
+                            _ => upstream.multiply(a.clone())?,
+                        };
+
+                        self.send(b, negate(numerator.divide(b.clone().multiply(b.clone())?)?)?)?;
+                    }
+                },
+            },
+            Node::Convolve(a, _, _) => {
+                self.send(a, upstream.deconvolve(a)?)?;
+            },
+            Node::ConvergeSum(a) => {
+                self.send(a, upstream.spread(a))?;
+            },
+            Node::Sigmoid(a) => self.propagate_activation(a, upstream, crate::ActivationFunction::Sigmoid)?,
+            Node::Tanh(a) => self.propagate_activation(a, upstream, crate::ActivationFunction::Tanh)?,
+            Node::Relu(a) => self.propagate_activation(a, upstream, crate::ActivationFunction::Relu)?,
+            Node::LeakyRelu(a, beta) => self.propagate_activation(a, upstream, crate::ActivationFunction::LeakyRelu(*beta))?,
+            Node::Elu(a) => self.propagate_activation(a, upstream, crate::ActivationFunction::Elu)?,
+            Node::Swish(a) => self.propagate_activation(a, upstream, crate::ActivationFunction::Swish)?,
+            Node::Softplus(a, beta) => self.propagate_activation(a, upstream, crate::ActivationFunction::Softplus(*beta))?,
+            Node::Transpose(a) => {
+                self.send(a, upstream.transpose()?)?;
+            },
+            Node::Sum(a) => {
+                self.send(a, upstream.broadcast(a.general_type, a.batch, a.convergent)?)?;
+            },
+            Node::Broadcast(a) => match (a.general_type, upstream.general_type) {
+                (GeneralType::Tensor(..), _) => {
+                    self.send(a, upstream)?;
+                },
+                (GeneralType::Element(ElementType(an, _)), GeneralType::Tensor(_, ElementType(bn, _))) if an == bn => {
+                    self.send(a, upstream.sum()?)?;
+                },
+                (GeneralType::Element(ElementType(an, _)), GeneralType::Element(ElementType(bn, _))) if an == bn => {
+                    self.send(a, upstream)?;
+                },
+                _ => {
+                    return Errors::UnsupportedOperand.into();
+                }
+            },
+            Node::Accumulate(a) => {
+                self.send(a, upstream.broadcast(a.general_type, a.batch, a.convergent)?)?;
+            },
+            Node::Reshape(a) => {
+                self.send(a, upstream.reshape(a.general_type.dimension())?)?;
+            },
+            Node::Pad(a, padding) => {
+                self.send(a, upstream.crop(*padding)?)?;
+            },
+            Node::Loss(loss, a, b) => {
+                // Targets are data, not something to be trained
+                if self.depends(b) {
+                    return Errors::UnsupportedOperand.into();
+                }
+
+                let upstream = match a.general_type {
+                    GeneralType::Tensor(_, element) | GeneralType::Element(element) if element.0 != 1 => {
+                        let batch = upstream.batch;
+                        upstream.broadcast(GeneralType::Element(element), batch, Convergent::None)?
+                    },
+                    _ => upstream,
+                };
+
+                self.send(a, a.clone().loss_gradient(b.clone(), *loss).multiply(upstream)?)?;
+            },
+            Node::Permute(a, axes) => {
+                let mut inverse = vec![0; axes.len()];
+
+                for (i, axis) in axes.iter().enumerate() {
+                    inverse[*axis as usize] = i as u32;
+                }
+
+                self.send(a, upstream.permute(&inverse)?)?;
+            },
+            Node::Concat(a, b, axis) => {
+                let end = dimension_along(a.general_type, *axis);
+
+                if self.depends(a) {
+                    self.send(a, upstream.clone().slice(*axis, 0, end)?)?;
+                }
+
+                if self.depends(b) {
+                    self.send(b, upstream.slice(*axis, end, dimension_along(b.general_type, *axis))?)?;
+                }
+            },
+            Node::Slice(a, axis, start) => {
+                // The elements cut away receive no gradient
+                let before = *start;
+                let after = dimension_along(a.general_type, *axis) - start - dimension_along(upstream.general_type, *axis);
+
+                let mut gradient = upstream;
+
+                if before != 0 {
+                    gradient = zeros_along(&gradient, *axis, before)?.concat(gradient, *axis)?;
+                }
+
+                if after != 0 {
+                    let zeros = zeros_along(&gradient, *axis, after)?;
+                    gradient = gradient.concat(zeros, *axis)?;
+                }
+
+                self.send(a, gradient)?;
+            },
+            Node::Spread(_) | Node::Deconvolve(..) | Node::Derivative(..) | Node::Crop(..) | Node::LossGradient(..) => {
+                return Errors::UnsupportedOperand.into();
+            },
+        }
+
+        Ok(())
+    }
+
+    fn propagate_activation(&mut self, operand: &Value, upstream: Value, activation_fn: crate::ActivationFunction) -> crate::Result<()> {
+        let derivative = operand.clone().derivative(activation_fn);
+
+        let gradient = match upstream.general_type {
+            GeneralType::Tensor(..) => upstream.hadamard_product(derivative)?,
+            GeneralType::Element(_) => upstream.multiply(derivative)?,
+        };
+
+        self.send(operand, gradient)
+    }
+}
+
+/// Element of the same type as the elements of `general_type` holding
+/// `value` in every channel
+fn constant_like(general_type: GeneralType, value: f64) -> crate::Result<Value> {
+    let element = match general_type {
+        GeneralType::Tensor(_, element) | GeneralType::Element(element) => element,
+    };
+
+    let scalar = Value::scalar(element.1, value);
+
+    if element.0 == 1 {
+        Ok(scalar)
+    } else {
+        scalar.broadcast(GeneralType::Element(element), 1, Convergent::None)
+    }
+}
+
+fn dimension_along(general_type: GeneralType, axis: u32) -> u32 {
+    general_type.dimension().axis(axis as usize)
+}
+
+/// Zeros shaped like `value` but `length` long along `axis`
+fn zeros_along(value: &Value, axis: u32, length: u32) -> crate::Result<Value> {
+    let general_type = match value.general_type {
+        GeneralType::Tensor(dimension, element) => GeneralType::Tensor(dimension.with_axis(axis as usize, length)?, element),
+        GeneralType::Element(_) => {
+            return Errors::RequiresTensor.into();
+        }
+    };
+
+    constant_like(general_type, 0.0)?.broadcast(general_type, value.batch, value.convergent)
+}
+
+fn negate(value: Value) -> crate::Result<Value> {
+    let minus_one = constant_like(value.general_type, -1.0)?;
+    value.multiply(minus_one)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ Dimension, Element, Engine, Graph, Kernel, Tensor, Value };
+
+    /// Distinct scalars away from zero and from the kinks of the activations
+    fn sample(dimension: Dimension, offset: usize) -> Vec<f64> {
+        (0..dimension.elements())
+            .map(|i| (((i + offset) * 7 % 5) as f64 - 2.0) * 0.37 + 0.1)
+            .collect()
+    }
+
+    /// Checks the gradients of the sum of `f` with respect to both of its
+    /// parameters against central differences of the same sum
+    fn check(a: Dimension, b: Dimension, f: impl Fn(Value, Value) -> crate::Result<Value>) {
+        let engine = Engine::new().unwrap();
+
+        let mut graph = Graph::new();
+        let x = graph.tensor::<f64>(a, 1);
+        let w = graph.tensor::<f64>(b, 1);
+
+        let y = f(x.clone(), w.clone()).unwrap();
+        let y = y.clone().sum().unwrap_or(y);
+        let gradients = y.gradient(&[&x, &w]).unwrap();
+
+        let forward: Kernel<Tensor<f64>, Element<f64>, Tensor<f64>> = engine.interpret(&y).unwrap();
+        let evaluate = |x: &[f64], w: &[f64]| {
+            let x = Tensor::from_slice(x, a, 1).unwrap();
+            let w = Tensor::from_slice(w, b, 1).unwrap();
+            forward.run(&x, &w).unwrap()[0]
+        };
+
+        let (x_values, w_values) = (sample(a, 0), sample(b, 3));
+        let input = Tensor::from_slice(&x_values, a, 1).unwrap();
+        let trainables = Tensor::from_slice(&w_values, b, 1).unwrap();
+
+        for (parameter, gradient) in gradients.iter().enumerate() {
+            let kernel: Kernel<Tensor<f64>, Tensor<f64>, Tensor<f64>> = engine.interpret(gradient).unwrap();
+            let analytic = kernel.run(&input, &trainables).unwrap();
+
+            for (i, analytic) in analytic.as_slice().iter().enumerate() {
+                let (mut above, mut below) = ((x_values.clone(), w_values.clone()), (x_values.clone(), w_values.clone()));
+                let epsilon = 1e-6;
+
+                if parameter == 0 {
+                    above.0[i] += epsilon;
+                    below.0[i] -= epsilon;
+                } else {
+                    above.1[i] += epsilon;
+                    below.1[i] -= epsilon;
+                }
+
+                let numeric = (evaluate(&above.0, &above.1) - evaluate(&below.0, &below.1)) / (2.0 * epsilon);
+                assert!((analytic - numeric).abs() < 1e-5 * numeric.abs().max(1.0),
+                    "parameter {} scalar {}: {} != {}", parameter, i, analytic, numeric);
+            }
+        }
+    }
+
+    #[test]
+    fn matrix_product() {
+        check(Dimension::new(3, 2, 1), Dimension::new(2, 4, 1), |x, w| x.multiply(w)?.sigmoid());
+    }
+
+    #[test]
+    fn element_wise() {
+        check(Dimension::new(2, 3, 1), Dimension::new(2, 3, 1), |x, w| {
+            x.clone().hadamard_product(w.clone())?
+                .subtract(w.clone().tanh()?)?
+                .divide(w.clone().sum()?)?
+                .add(x.swish()?)?
+                .add(w.softplus(2.0)?)
+        });
+    }
+
+    #[test]
+    fn mixed_operands() {
+        check(Dimension::new(3, 2, 1), Dimension::new(2, 2, 1), |x, w| {
+            let scale = w.clone().sigmoid()?.sum()?;
+            x.multiply(scale)?.tanh()
+        });
+
+        check(Dimension::new(2, 2, 1), Dimension::new(3, 2, 1), |x, w| {
+            x.tanh()?.sum()?.divide(w.sigmoid()?)
+        });
+    }
+
+    #[test]
+    fn convolution() {
+        // Windows of 3 x 2 every 2 along both axes of the padded 7 x 6
+        // input, overlapping along the first axis and reading the padding
+        check(Dimension::new(5, 4, 1), Dimension::new(3, 2, 1), |x, w| {
+            x.pad((1, 1))?
+                .convolve((3, 2), (2, 2))?
+                .hadamard_product(w)?
+                .sum()?
+                .converge_sum()?
+                .tanh()
+        });
+    }
+
+    #[test]
+    fn convolution_of_planes() {
+        // Three filters over windows of 2 x 2 x 2 every 1 then 2 positions,
+        // laid out the way `Conv2d` multiplies them
+        check(Dimension::new(4, 5, 2), Dimension::new(8, 3, 1), |x, w| {
+            x.convolve((2, 2), (1, 2))?
+                .reshape(Dimension::new(1, 8, 1))?
+                .multiply(w)?
+                .reshape(Dimension::new(1, 1, 3))?
+                .converge_sum()?
+                .sigmoid()
+        });
+    }
+
+    #[test]
+    fn activations() {
+        check(Dimension::new(5, 1, 1), Dimension::new(5, 1, 1), |x, w| {
+            x.clone().relu()?
+                .add(x.clone().leaky_relu(0.1)?)?
+                .add(x.elu()?)?
+                .hadamard_product(w)
+        });
+    }
+
+    #[test]
+    fn shared_operands() {
+        check(Dimension::new(4, 1, 1), Dimension::new(4, 1, 1), |x, w| {
+            let h = x.clone().hadamard_product(w)?.tanh()?;
+            h.clone().hadamard_product(h.clone())?.add(h)?.hadamard_product(x)
+        });
+    }
+
+    #[test]
+    fn losses() {
+        // Targets can't be differentiated against, so the trainables scale the loss instead
+        check(Dimension::new(4, 1, 1), Dimension::new(4, 1, 1), |x, w| {
+            let target = Tensor::from_vec(vec![0.2, 0.9, 0.4, 0.7], Dimension::new(4, 1, 1), 1)?;
+
+            x.clone().mean_squared_error(target.clone())?
+                .add(x.clone().huber(target.clone(), 0.5)?)?
+                .add(x.sigmoid()?.binary_cross_entropy(target)?)?
+                .multiply(w.sum()?)
+        });
+    }
+
+    #[test]
+    fn shapes() {
+        check(Dimension::new(2, 3, 1), Dimension::new(3, 2, 1), |x, w| {
+            let product = x.transpose()?.hadamard_product(w.clone())?;
+            product.concat(w, 0)?.sigmoid()
+        });
+    }
+}
@@ -0,0 +1,60 @@
+use super::{ Value, GeneralType, ElementType };
+
+/// Hands out the placeholders a graph reads, numbering them in the order
+/// they are declared. That order is the one the kernel compiled from the
+/// graph takes its parameters in.
+#[derive(Default)]
+pub struct Graph {
+    general_types: Vec<GeneralType>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Next parameter, read from a buffer of `general_type`
+    pub fn input(&mut self, general_type: GeneralType) -> Value {
+        let value = Value::input(self.general_types.len() as u32, general_type);
+        self.general_types.push(general_type);
+        value
+    }
+
+    /// Next parameter, holding `batch` samples of `general_type`
+    pub fn batched_input(&mut self, general_type: GeneralType, batch: u32) -> crate::Result<Value> {
+        let value = Value::batched_input(self.general_types.len() as u32, general_type, batch)?;
+        self.general_types.push(general_type.batched(batch)?);
+        Ok(value)
+    }
+
+    /// Next parameter, a tensor of `dimension` with `channels` channels
+    /// of `F`
+    pub fn tensor<F: crate::Scalar>(&mut self, dimension: crate::Dimension, channels: u32) -> Value {
+        self.input(GeneralType::Tensor(dimension, ElementType(channels, F::scalar_type())))
+    }
+
+    /// Next parameter, an element of `channels` channels of `F`
+    pub fn element<F: crate::Scalar>(&mut self, channels: u32) -> Value {
+        self.input(GeneralType::Element(ElementType(channels, F::scalar_type())))
+    }
+
+    /// Next parameters, one for every buffer of `value`
+    pub fn like<V: crate::LayerValue>(&mut self, value: &V) -> Vec<Value> {
+        value.general_types()
+            .into_iter()
+            .map(|general_type| self.input(general_type))
+            .collect()
+    }
+
+    /// Next parameters, read as `trainables` are by their layer
+    pub fn trainables<T: crate::LayerTrainables>(&mut self, trainables: &T) -> T::Values {
+        let values = trainables.values(self.general_types.len() as u32);
+        self.general_types.extend(trainables.general_types());
+        values
+    }
+
+    /// Types of the parameters declared so far, in order
+    pub fn signature(&self) -> &[GeneralType] {
+        &self.general_types
+    }
+}
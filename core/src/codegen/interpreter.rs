@@ -0,0 +1,664 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use super::{
+    Value,
+    Operand,
+    Node,
+    GeneralType,
+};
+
+use super::layout::Layout;
+use crate::Errors;
+
+/// Evaluates `Value` graphs by walking them directly, without generating
+/// any code. Slow, but straightforward enough to serve as a reference for
+/// the compiled kernels and to run where LLVM is unavailable.
+///
+/// Every scalar is evaluated as an `f64` and only converted back to its type
+/// when written out, so it is a reference for float graphs only. Integer
+/// results don't wrap on overflow like compiled kernels do, and 64 bit
+/// integers beyond 2^53 lose precision.
+pub(crate) struct Interpreter<'a> {
+    parameters: &'a [*const u8],
+    /// Result of every node evaluated so far, by id
+    results: RefCell<HashMap<usize, Buffer>>,
+}
+
+/// Scalars of an intermediate value, held as `f64` whatever their type
+#[derive(Clone)]
+struct Buffer {
+    data: Vec<f64>,
+    layout: Layout,
+}
+
+impl<'a> Interpreter<'a> {
+    /// Evaluates each of `outputs` into the buffer at the matching index of
+    /// `destinations`
+    ///
+    /// # Safety
+    /// Every buffer must be valid for the layout of the parameter or output
+    /// at the same index
+    pub unsafe fn run(outputs: &[&Value], parameters: &'a [*const u8], destinations: &[*mut u8]) -> crate::Result<()> {
+        let interpreter = Self {
+            parameters,
+            results: RefCell::new(HashMap::new()),
+        };
+
+        for (output, destination) in outputs.iter().zip(destinations.iter()) {
+            let buffer = interpreter.evaluate(output)?;
+
+            for (i, value) in buffer.data.iter().enumerate() {
+                buffer.layout.scalar.write(*destination, i, *value);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Scalars of `value`, which must not read any parameter
+    pub fn constant(value: &Value) -> crate::Result<Vec<f64>> {
+        let interpreter = Interpreter {
+            parameters: &[],
+            results: RefCell::new(HashMap::new()),
+        };
+
+        Ok(interpreter.evaluate(value)?.data)
+    }
+
+    /// Evaluates `value`, nodes shared by several values only once
+    fn evaluate(&self, value: &Value) -> crate::Result<Buffer> {
+        let id = match value.id() {
+            Some(id) => id,
+            None => return self.compute(value),
+        };
+
+        if let Some(buffer) = self.results.borrow().get(&id) {
+            return Ok(buffer.clone());
+        }
+
+        let buffer = self.compute(value)?;
+        self.results.borrow_mut().insert(id, buffer.clone());
+
+        Ok(buffer)
+    }
+
+    fn compute(&self, value: &Value) -> crate::Result<Buffer> {
+        let layout = Layout::of(value);
+
+        let data = match &value.inner {
+            Operand::Parameter(index, _) => {
+                let pointer = *self.parameters.get(*index as usize)
+                    .ok_or(crate::Error::from(Errors::MissingKernelParameter))?;
+
+                (0..layout.len() as usize)
+                    .map(|i| unsafe { layout.scalar.read(pointer, i) })
+                    .collect()
+            },
+            Operand::Constant(constant) => constant.scalars(),
+            Operand::Node(node) => match node.as_ref() {
+                Node::Add(a, b) => self.elementwise(a, b, &layout, |x, y| x + y)?,
+                Node::Subtract(a, b) => self.elementwise(a, b, &layout, |x, y| x - y)?,
+                Node::HadamardProduct(a, b) => self.elementwise(a, b, &layout, |x, y| x * y)?,
+                Node::Divide(a, b) => if layout.scalar.is_float() {
+                    self.elementwise(a, b, &layout, |x, y| x / y)?
+                } else {
+                    self.elementwise(a, b, &layout, |x, y| (x / y).trunc())?
+                },
+                Node::Multiply(a, b) => match (a.general_type, b.general_type) {
+                    (GeneralType::Tensor(..), GeneralType::Tensor(..)) => self.matrix_product(a, b, &layout)?,
+                    _ => self.elementwise(a, b, &layout, |x, y| x * y)?,
+                },
+                Node::Convolve(operand, _, stride) => self.convolve(operand, *stride, &layout)?,
+                Node::ConvergeSum(operand) => self.converge_sum(operand, &layout)?,
+                Node::Sigmoid(operand) => self.activation(operand, &layout, sigmoid)?,
+                Node::Tanh(operand) => self.activation(operand, &layout, f64::tanh)?,
+                Node::Relu(operand) => self.activation(operand, &layout, |x| if x > 0.0 { x } else { 0.0 })?,
+                Node::LeakyRelu(operand, beta) => self.activation(operand, &layout, |x| if x > 0.0 { x } else { *beta as f64 * x })?,
+                Node::Elu(operand) => self.activation(operand, &layout, |x| if x > 0.0 { x } else { x.exp() - 1.0 })?,
+                Node::Swish(operand) => self.activation(operand, &layout, |x| x * sigmoid(x))?,
+                Node::Softplus(operand, beta) => self.activation(operand, &layout, |x| (1.0 + (*beta as f64 * x).exp()).ln() / *beta as f64)?,
+                Node::Transpose(operand) => self.transpose(operand, &layout)?,
+                Node::Sum(operand) => self.sum(operand, &layout)?,
+                Node::Broadcast(operand) => {
+                    let input = self.evaluate(operand)?;
+
+                    (0..layout.len())
+                        .map(|i| input.data[broadcast(&input.layout, &layout, i)])
+                        .collect()
+                },
+                Node::Accumulate(operand) => {
+                    let input = self.evaluate(operand)?;
+                    let mut data = vec![0.0; layout.len() as usize];
+
+                    // Every scalar adds onto the one it would be broadcast from
+                    for (i, value) in input.data.iter().enumerate() {
+                        data[broadcast(&layout, &input.layout, i as u64)] += *value;
+                    }
+
+                    data
+                },
+                Node::Spread(operand) => self.spread(operand, &layout)?,
+                Node::Deconvolve(operand, _, stride) => self.deconvolve(operand, *stride, &layout)?,
+                Node::Derivative(activation_fn, operand) => self.activation(operand, &layout, |x| derivative(activation_fn, x))?,
+                Node::Reshape(operand) => self.evaluate(operand)?.data,
+                Node::Pad(operand, padding) => {
+                    let input = self.evaluate(operand)?;
+                    let mut data = vec![0.0; layout.len() as usize];
+
+                    for (i, value) in input.data.iter().enumerate() {
+                        data[shift(&input.layout, &layout, i as u64, (padding.0 as u64, padding.1 as u64, 0))] = *value;
+                    }
+
+                    data
+                },
+                Node::Loss(loss, prediction, target) => {
+                    let prediction = self.evaluate(prediction)?;
+                    let target = self.evaluate(target)?;
+
+                    (0..layout.batch)
+                        .map(|i| {
+                            let (prediction, target) = (sample(&prediction, i), sample(&target, i));
+
+                            match loss {
+                                crate::Loss::CategoricalCrossEntropy => {
+                                    let (maximum, exponents, _) = softmax_statistics(prediction, target);
+                                    let log_sum = maximum + exponents.ln();
+
+                                    Ok(prediction.iter().zip(target.iter())
+                                        .map(|(x, y)| y * (log_sum - x))
+                                        .sum())
+                                },
+                                _ => Ok(prediction.iter().zip(target.iter())
+                                    .map(|(x, y)| loss_term(loss, *x, *y))
+                                    .sum::<crate::Result<f64>>()? / prediction.len() as f64),
+                            }
+                        })
+                        .collect::<crate::Result<_>>()?
+                },
+                Node::LossGradient(loss, prediction, target) => {
+                    let prediction = self.evaluate(prediction)?;
+                    let target = self.evaluate(target)?;
+                    let mut data = Vec::with_capacity(layout.len() as usize);
+
+                    for i in 0..layout.batch {
+                        let (prediction, target) = (sample(&prediction, i), sample(&target, i));
+                        let count = prediction.len() as f64;
+
+                        match loss {
+                            crate::Loss::CategoricalCrossEntropy => {
+                                let (maximum, exponents, targets) = softmax_statistics(prediction, target);
+
+                                data.extend(prediction.iter().zip(target.iter())
+                                    .map(|(x, y)| (x - maximum).exp() / exponents * targets - y));
+                            },
+                            _ => {
+                                for (x, y) in prediction.iter().zip(target.iter()) {
+                                    data.push(loss_derivative(loss, *x, *y)? / count);
+                                }
+                            },
+                        }
+                    }
+
+                    data
+                },
+                Node::Crop(operand, padding) => {
+                    let input = self.evaluate(operand)?;
+
+                    (0..layout.len())
+                        .map(|i| input.data[shift(&layout, &input.layout, i, (padding.0 as u64, padding.1 as u64, 0))])
+                        .collect()
+                },
+                Node::Permute(operand, axes) => {
+                    let input = self.evaluate(operand)?;
+                    let elements = layout.dimension.elements() as u64;
+
+                    (0..layout.len())
+                        .map(|i| {
+                            let channel = i % layout.channels;
+                            let element = i / layout.channels % elements;
+                            let block = i / layout.channels / elements;
+
+                            // Axis `k` of the output is axis `axes[k]` of the input
+                            let index = layout.dimension.index_at(element as usize);
+                            let mut source = vec![0; axes.len()];
+
+                            for (k, axis) in axes.iter().enumerate() {
+                                source[*axis as usize] = index[k];
+                            }
+
+                            let source = crate::Dimension::from_axes(&source)?;
+                            let position = input.layout.dimension.position(&source)
+                                .ok_or(crate::Error::from(Errors::InvalidAxis))?;
+
+                            let base = base(&input.layout, &layout, block);
+                            Ok(input.data[(base + position as u64 * layout.channels + channel) as usize])
+                        })
+                        .collect::<crate::Result<_>>()?
+                },
+                Node::Concat(a, b, axis) => {
+                    let a = self.evaluate(a)?;
+                    let b = self.evaluate(b)?;
+                    let mut data = vec![0.0; layout.len() as usize];
+
+                    // `b` starts where `a` ends along the axis
+                    let end = a.layout.dimension.axis(*axis as usize) as u64;
+                    let around = layout.around(*axis);
+
+                    for (i, value) in a.data.iter().enumerate() {
+                        data[shift(&a.layout.around(*axis), &around, i as u64, (0, 0, 0))] = *value;
+                    }
+
+                    for (i, value) in b.data.iter().enumerate() {
+                        data[shift(&b.layout.around(*axis), &around, i as u64, (0, end, 0))] = *value;
+                    }
+
+                    data
+                },
+                Node::Slice(operand, axis, start) => {
+                    let input = self.evaluate(operand)?;
+                    let (from, to) = (layout.around(*axis), input.layout.around(*axis));
+
+                    (0..layout.len())
+                        .map(|i| input.data[shift(&from, &to, i, (0, *start as u64, 0))])
+                        .collect()
+                },
+            },
+        };
+
+        Ok(Buffer { data, layout })
+    }
+
+    fn elementwise(&self, a: &Value, b: &Value, layout: &Layout, function: impl Fn(f64, f64) -> f64) -> crate::Result<Vec<f64>> {
+        let a = self.evaluate(a)?;
+        let b = self.evaluate(b)?;
+
+        Ok((0..layout.len())
+            .map(|i| function(
+                a.data[broadcast(&a.layout, layout, i)],
+                b.data[broadcast(&b.layout, layout, i)]))
+            .collect())
+    }
+
+    fn activation(&self, operand: &Value, layout: &Layout, function: impl Fn(f64) -> f64) -> crate::Result<Vec<f64>> {
+        if !layout.scalar.is_float() {
+            return Errors::InvalidOperandTypes.into();
+        }
+
+        let operand = self.evaluate(operand)?;
+
+        Ok(operand.data.into_iter().map(function).collect())
+    }
+
+    fn matrix_product(&self, a: &Value, b: &Value, layout: &Layout) -> crate::Result<Vec<f64>> {
+        let a = self.evaluate(a)?;
+        let b = self.evaluate(b)?;
+
+        let shared = a.layout.shape.1;
+
+        Ok((0..layout.len())
+            .map(|i| {
+                let (channel, row, column, plane, block) = decompose(layout, i);
+                let (a_base, b_base) = (base(&a.layout, layout, block), base(&b.layout, layout, block));
+
+                (0..shared)
+                    .map(|k| a.data[offset(&a.layout, a_base, row, k, plane, channel)]
+                        * b.data[offset(&b.layout, b_base, k, column, plane, channel)])
+                    .sum()
+            })
+            .collect())
+    }
+
+    fn convolve(&self, operand: &Value, stride: (u32, u32), layout: &Layout) -> crate::Result<Vec<f64>> {
+        let input = self.evaluate(operand)?;
+        let positions_x = (input.layout.shape.0 - layout.shape.0) / stride.0 as u64 + 1;
+
+        Ok((0..layout.len())
+            .map(|i| {
+                let (channel, x, y, plane, block) = decompose(layout, i);
+                let window = block % layout.outer;
+                let x = window % positions_x * stride.0 as u64 + x;
+                let y = window / positions_x * stride.1 as u64 + y;
+
+                input.data[offset(&input.layout, base(&input.layout, layout, block), x, y, plane, channel)]
+            })
+            .collect())
+    }
+
+    fn converge_sum(&self, operand: &Value, layout: &Layout) -> crate::Result<Vec<f64>> {
+        let input = self.evaluate(operand)?;
+        let area = input.layout.shape.0 * input.layout.shape.1;
+
+        Ok((0..layout.len())
+            .map(|i| {
+                let (channel, x, y, plane, sample) = decompose(layout, i);
+                let window = sample * input.layout.outer + x + y * layout.shape.0;
+                let base = window * input.layout.inner() + plane * area * layout.channels + channel;
+
+                (0..area)
+                    .map(|k| input.data[(base + k * layout.channels) as usize])
+                    .sum()
+            })
+            .collect())
+    }
+
+    fn transpose(&self, operand: &Value, layout: &Layout) -> crate::Result<Vec<f64>> {
+        let input = self.evaluate(operand)?;
+
+        Ok((0..layout.len())
+            .map(|i| {
+                let (channel, row, column, plane, block) = decompose(layout, i);
+                input.data[offset(&input.layout, base(&input.layout, layout, block), column, row, plane, channel)]
+            })
+            .collect())
+    }
+
+    fn sum(&self, operand: &Value, layout: &Layout) -> crate::Result<Vec<f64>> {
+        let input = self.evaluate(operand)?;
+        let count = input.layout.shape.0 * input.layout.shape.1 * input.layout.shape.2;
+
+        Ok((0..layout.len())
+            .map(|i| {
+                let channel = i % layout.channels;
+                let block = i / layout.channels;
+                let base = block * input.layout.inner() + channel;
+
+                (0..count)
+                    .map(|k| input.data[(base + k * layout.channels) as usize])
+                    .sum()
+            })
+            .collect())
+    }
+
+    fn spread(&self, operand: &Value, layout: &Layout) -> crate::Result<Vec<f64>> {
+        let input = self.evaluate(operand)?;
+        let positions_x = input.layout.shape.0;
+
+        Ok((0..layout.len())
+            .map(|i| {
+                let (channel, _, _, plane, block) = decompose(layout, i);
+                let window = block % layout.outer;
+
+                input.data[offset(&input.layout, base(&input.layout, layout, block),
+                    window % positions_x, window / positions_x, plane, channel)]
+            })
+            .collect())
+    }
+
+    fn deconvolve(&self, operand: &Value, stride: (u32, u32), layout: &Layout) -> crate::Result<Vec<f64>> {
+        let input = self.evaluate(operand)?;
+        let positions_x = (layout.shape.0 - input.layout.shape.0) / stride.0 as u64 + 1;
+        let mut data = vec![0.0; layout.len() as usize];
+
+        for i in 0..input.layout.len() {
+            let (channel, x, y, plane, block) = decompose(&input.layout, i);
+            let window = block % input.layout.outer;
+            let x = window % positions_x * stride.0 as u64 + x;
+            let y = window / positions_x * stride.1 as u64 + y;
+
+            data[offset(layout, base(layout, &input.layout, block), x, y, plane, channel)] += input.data[i as usize];
+        }
+
+        Ok(data)
+    }
+}
+
+/// Splits an index into `layout` into its channel, x, y, z and block, the
+/// window counted over every sample
+fn decompose(layout: &Layout, index: u64) -> (u64, u64, u64, u64, u64) {
+    let channel = index % layout.channels;
+    let rest = index / layout.channels;
+    let x = rest % layout.shape.0;
+    let rest = rest / layout.shape.0;
+    let y = rest % layout.shape.1;
+    let rest = rest / layout.shape.1;
+    let z = rest % layout.shape.2;
+    let block = rest / layout.shape.2;
+
+    (channel, x, y, z, block)
+}
+
+/// Offset of the window of `operand` read for `block` of `output`, values
+/// without windows or samples repeating their only one
+fn base(operand: &Layout, output: &Layout, block: u64) -> u64 {
+    let sample = if operand.batch > 1 { block / output.outer } else { 0 };
+    let window = if operand.outer > 1 { block % output.outer } else { 0 };
+
+    (sample * operand.outer + window) * operand.inner()
+}
+
+fn offset(layout: &Layout, base: u64, x: u64, y: u64, z: u64, channel: u64) -> usize {
+    let element = x + y * layout.shape.0 + z * layout.shape.0 * layout.shape.1;
+
+    (base + element * layout.channels + channel) as usize
+}
+
+/// Maps an index into `from` onto the index into `to` of the element moved
+/// `shift` further along each axis
+fn shift(from: &Layout, to: &Layout, index: u64, shift: (u64, u64, u64)) -> usize {
+    let (channel, x, y, z, block) = decompose(from, index);
+
+    offset(to, base(to, from, block), x + shift.0, y + shift.1, z + shift.2, channel)
+}
+
+/// Maps an index into `output` onto the matching index into `operand`,
+/// repeating elements over tensors, windowless values over windows and
+/// single samples over samples
+fn broadcast(operand: &Layout, output: &Layout, index: u64) -> usize {
+    let inner = index % output.inner();
+
+    let inner = if operand.element {
+        inner % operand.channels
+    } else {
+        inner
+    };
+
+    (base(operand, output, index / output.inner()) + inner) as usize
+}
+
+/// Scalars of sample `index` of `buffer`, or of its only sample
+fn sample(buffer: &Buffer, index: u64) -> &[f64] {
+    let size = (buffer.layout.len() / buffer.layout.batch) as usize;
+    let start = if buffer.layout.batch > 1 { index as usize * size } else { 0 };
+
+    &buffer.data[start..start + size]
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+fn derivative(activation_fn: &crate::ActivationFunction, x: f64) -> f64 {
+    match activation_fn {
+        crate::ActivationFunction::Sigmoid => sigmoid(x) * (1.0 - sigmoid(x)),
+        crate::ActivationFunction::Tanh => 1.0 - x.tanh() * x.tanh(),
+        crate::ActivationFunction::Relu => if x > 0.0 { 1.0 } else { 0.0 },
+        crate::ActivationFunction::LeakyRelu(beta) => if x > 0.0 { 1.0 } else { *beta as f64 },
+        crate::ActivationFunction::Elu => if x > 0.0 { 1.0 } else { x.exp() },
+        crate::ActivationFunction::Swish => sigmoid(x) * (1.0 + x * (1.0 - sigmoid(x))),
+        crate::ActivationFunction::Softplus(beta) => sigmoid(*beta as f64 * x),
+    }
+}
+
+fn loss_term(loss: &crate::Loss, x: f64, y: f64) -> crate::Result<f64> {
+    let difference = x - y;
+
+    match loss {
+        crate::Loss::MeanSquaredError => Ok(difference * difference),
+        crate::Loss::MeanAbsoluteError => Ok(difference.abs()),
+        crate::Loss::BinaryCrossEntropy => {
+            let p = clamp_probability(x);
+            Ok(-(y * p.ln() + (1.0 - y) * (1.0 - p).ln()))
+        },
+        crate::Loss::Huber(delta) => {
+            let delta = *delta as f64;
+
+            if difference.abs() <= delta {
+                Ok(0.5 * difference * difference)
+            } else {
+                Ok(delta * (difference.abs() - 0.5 * delta))
+            }
+        },
+        // Depends on the whole sample rather than on a single scalar
+        crate::Loss::CategoricalCrossEntropy => Errors::UnsupportedOperand.into(),
+    }
+}
+
+fn loss_derivative(loss: &crate::Loss, x: f64, y: f64) -> crate::Result<f64> {
+    let difference = x - y;
+
+    match loss {
+        crate::Loss::MeanSquaredError => Ok(2.0 * difference),
+        crate::Loss::MeanAbsoluteError => Ok(sign(difference)),
+        crate::Loss::BinaryCrossEntropy => {
+            let p = clamp_probability(x);
+            Ok((p - y) / (p * (1.0 - p)))
+        },
+        crate::Loss::Huber(delta) => {
+            let delta = *delta as f64;
+
+            if difference.abs() <= delta {
+                Ok(difference)
+            } else {
+                Ok(delta * sign(difference))
+            }
+        },
+        // Depends on the whole sample rather than on a single scalar
+        crate::Loss::CategoricalCrossEntropy => Errors::UnsupportedOperand.into(),
+    }
+}
+
+/// Maximum of `prediction`, sum of `e^(p - maximum)` over it and sum of
+/// `target`
+fn softmax_statistics(prediction: &[f64], target: &[f64]) -> (f64, f64, f64) {
+    let maximum = prediction.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let exponents = prediction.iter().map(|x| (x - maximum).exp()).sum();
+
+    (maximum, exponents, target.iter().sum())
+}
+
+fn clamp_probability(p: f64) -> f64 {
+    p.clamp(1e-7, 1.0 - 1e-7)
+}
+
+fn sign(x: f64) -> f64 {
+    if x > 0.0 {
+        1.0
+    } else if x < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(all(test, feature = "llvm"))]
+mod tests {
+    use crate::{ Dimension, Element, Engine, Graph, Kernel, LayerValue, Tensor, Value };
+
+    fn sample(dimension: Dimension, offset: usize) -> Tensor<f64> {
+        let data = (0..dimension.elements())
+            .map(|i| (((i + offset) * 7 % 5) as f64 - 2.0) * 0.37 + 0.1)
+            .collect();
+
+        Tensor::from_vec(data, dimension, 1).unwrap()
+    }
+
+    /// Checks that the compiled kernel of `value` and the interpreter agree
+    fn same<O: LayerValue>(value: &Value, input: &Tensor<f64>, trainables: &Tensor<f64>, scalars: fn(&O) -> &[f64]) {
+        let engine = Engine::new().unwrap();
+        let compiled: Kernel<Tensor<f64>, O, Tensor<f64>> = engine.compile(value).unwrap();
+        let interpreted: Kernel<Tensor<f64>, O, Tensor<f64>> = engine.interpret(value).unwrap();
+
+        let (compiled, interpreted) = (compiled.run(input, trainables).unwrap(), interpreted.run(input, trainables).unwrap());
+
+        for (a, b) in scalars(&compiled).iter().zip(scalars(&interpreted)) {
+            assert!((a - b).abs() <= 1e-9 * b.abs().max(1.0), "{} != {}", a, b);
+        }
+    }
+
+    /// Compares `f` of both parameters and its gradients with respect to them
+    fn compare(a: Dimension, b: Dimension, f: impl Fn(Value, Value) -> crate::Result<Value>) {
+        let mut graph = Graph::new();
+        let x = graph.tensor::<f64>(a, 1);
+        let w = graph.tensor::<f64>(b, 1);
+        let (input, trainables) = (sample(a, 0), sample(b, 3));
+
+        let y = f(x.clone(), w.clone()).unwrap();
+
+        match y.clone().sum() {
+            Ok(sum) => {
+                same(&y, &input, &trainables, Tensor::<f64>::as_slice);
+
+                for gradient in sum.gradient(&[&x, &w]).unwrap() {
+                    same(&gradient, &input, &trainables, Tensor::<f64>::as_slice);
+                }
+            },
+            Err(_) => {
+                same(&y, &input, &trainables, Element::<f64>::as_slice);
+
+                for gradient in y.gradient(&[&x, &w]).unwrap() {
+                    same(&gradient, &input, &trainables, Tensor::<f64>::as_slice);
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn matrix_product() {
+        compare(Dimension::new(4, 3, 1), Dimension::new(3, 2, 1), |x, w| x.multiply(w)?.sigmoid());
+    }
+
+    #[test]
+    fn element_wise() {
+        compare(Dimension::new(3, 2, 1), Dimension::new(3, 2, 1), |x, w| {
+            x.clone().hadamard_product(w.clone())?
+                .subtract(w.clone())?
+                .divide(w.sum()?)?
+                .add(x)
+        });
+    }
+
+    #[test]
+    fn activations() {
+        compare(Dimension::new(5, 1, 1), Dimension::new(5, 1, 1), |x, w| {
+            let mut y = x.clone().sigmoid()?.add(x.clone().tanh()?)?;
+
+            for activation in [x.clone().relu()?, x.clone().leaky_relu(0.1)?, x.clone().elu()?, x.clone().swish()?, x.softplus(2.0)?] {
+                y = y.add(activation)?;
+            }
+
+            y.hadamard_product(w)
+        });
+    }
+
+    #[test]
+    fn losses() {
+        let target = Tensor::from_vec(vec![0.1, 0.6, 0.3], Dimension::new(3, 1, 1), 1).unwrap();
+
+        for loss in [
+            crate::Loss::MeanSquaredError,
+            crate::Loss::MeanAbsoluteError,
+            crate::Loss::BinaryCrossEntropy,
+            crate::Loss::CategoricalCrossEntropy,
+            crate::Loss::Huber(0.5),
+        ] {
+            compare(Dimension::new(3, 1, 1), Dimension::new(3, 1, 1), |x, w| {
+                x.hadamard_product(w)?.sigmoid()?.loss(target.clone(), &loss)
+            });
+        }
+    }
+
+    #[test]
+    fn convolution() {
+        compare(Dimension::new(5, 4, 1), Dimension::new(2, 2, 1), |x, w| {
+            x.convolve((2, 2), (1, 1))?.hadamard_product(w)?.converge_sum()?.relu()
+        });
+    }
+
+    #[test]
+    fn shapes() {
+        compare(Dimension::new(2, 3, 1), Dimension::new(3, 2, 1), |x, w| {
+            x.transpose()?.concat(w, 0)?.reshape(Dimension::new(4, 3, 1))?.tanh()
+        });
+    }
+}
@@ -0,0 +1,80 @@
+use super::{
+    Value,
+    GeneralType,
+    ElementType,
+    ScalarType,
+};
+
+/// Memory layout of a value while a graph is evaluated, shared by every
+/// backend. `batch` counts the samples of the value, one after the other,
+/// and `outer` the convolution windows each sample of a convergent value is
+/// evaluated over, each window holding `inner()` scalars laid out as
+/// `x + y * X + z * X * Y` elements of `channels` interleaved scalars, where
+/// `z` runs over every axis from the third on.
+#[derive(Clone, Copy)]
+#[derive(PartialEq)]
+pub(super) struct Layout {
+    pub batch: u64,
+    pub outer: u64,
+    pub shape: (u64, u64, u64),
+    pub dimension: crate::Dimension,
+    pub channels: u64,
+    pub scalar: ScalarType,
+    pub element: bool,
+}
+
+impl Layout {
+    pub fn of(value: &Value) -> Self {
+        let batch = value.batch as u64;
+        let outer = value.convergent.positions() as u64;
+
+        match value.general_type {
+            GeneralType::Tensor(dimension, ElementType(channels, scalar)) => Self {
+                batch,
+                outer,
+                shape: (dimension.x() as u64, dimension.y() as u64, dimension.z() as u64),
+                dimension,
+                channels: channels as u64,
+                scalar,
+                element: false,
+            },
+            GeneralType::Element(ElementType(channels, scalar)) => Self {
+                batch,
+                outer,
+                shape: (1, 1, 1),
+                dimension: crate::Dimension::new(1, 1, 1),
+                channels: channels as u64,
+                scalar,
+                element: true,
+            },
+        }
+    }
+
+    pub fn inner(&self) -> u64 {
+        self.shape.0 * self.shape.1 * self.shape.2 * self.channels
+    }
+
+    /// Number of windows over every sample
+    pub fn blocks(&self) -> u64 {
+        self.batch * self.outer
+    }
+
+    pub fn len(&self) -> u64 {
+        self.blocks() * self.inner()
+    }
+
+    /// Same scalars seen as the elements before `axis`, those along it and
+    /// those after it, so that `axis` becomes the second one
+    pub fn around(&self, axis: u32) -> Self {
+        let axes = self.dimension.axes();
+        let axis = axis as usize;
+
+        let before = axes[..axis.min(axes.len())].iter().map(|size| *size as u64).product();
+        let after = axes.iter().skip(axis + 1).map(|size| *size as u64).product();
+
+        Self {
+            shape: (before, self.dimension.axis(axis) as u64, after),
+            ..*self
+        }
+    }
+}
@@ -1,4 +1,9 @@
+#[cfg(feature = "llvm")]
 mod compiler;
+#[cfg(feature = "llvm")]
+mod fusion;
+mod interpreter;
+mod layout;
 mod value;
 mod general_type;
 mod scalar_type;
@@ -6,11 +11,20 @@ mod node;
 mod constant;
 mod element_type;
 mod operand;
+mod gradient;
+mod graph;
+mod pass;
+mod passes;
 
 pub use value::Value;
-use general_type::GeneralType;
-use scalar_type::ScalarType;
+pub use graph::Graph;
+#[cfg(feature = "llvm")]
+pub(crate) use compiler::Compiler;
+pub(crate) use interpreter::Interpreter;
+pub use general_type::GeneralType;
+pub use scalar_type::ScalarType;
 use node::Node;
-use element_type::ElementType;
+pub use element_type::ElementType;
 use constant::Constant;
+pub(crate) use pass::PassManager;
 use operand::Operand;
\ No newline at end of file
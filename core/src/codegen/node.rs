@@ -1,17 +1,107 @@
+#[derive(Clone)]
 #[derive(PartialEq)]
 pub enum Node {
-    Add(super::Operand, super::Operand),
-    Subtract(super::Operand, super::Operand),
-    Divide(super::Operand, super::Operand),
-    Multiply(super::Operand, super::Operand),
-    HadamardProduct(super::Operand, super::Operand),
-    Convolve(super::Operand, (u32, u32), (u32, u32)),
-    ConvergeSum(super::Operand),
-    Sigmoid(super::Operand),
-    Tanh(super::Operand),
-    Relu(super::Operand),
-    LeakyRelu(super::Operand, f32),
-    Elu(super::Operand),
-    Swish(super::Operand),
-    Softplus(super::Operand, f32),
-}
\ No newline at end of file
+    Add(super::Value, super::Value),
+    Subtract(super::Value, super::Value),
+    Divide(super::Value, super::Value),
+    Multiply(super::Value, super::Value),
+    HadamardProduct(super::Value, super::Value),
+    Convolve(super::Value, (u32, u32), (u32, u32)),
+    ConvergeSum(super::Value),
+    Sigmoid(super::Value),
+    Tanh(super::Value),
+    Relu(super::Value),
+    LeakyRelu(super::Value, f32),
+    Elu(super::Value),
+    Swish(super::Value),
+    Softplus(super::Value, f32),
+    Transpose(super::Value),
+    Sum(super::Value),
+    Broadcast(super::Value),
+    Accumulate(super::Value),
+    Spread(super::Value),
+    Deconvolve(super::Value, (u32, u32), (u32, u32)),
+    Derivative(crate::ActivationFunction, super::Value),
+    Reshape(super::Value),
+    Pad(super::Value, (u32, u32)),
+    Crop(super::Value, (u32, u32)),
+    Permute(super::Value, Vec<u32>),
+    Concat(super::Value, super::Value, u32),
+    Slice(super::Value, u32, u32),
+    Loss(crate::Loss, super::Value, super::Value),
+    LossGradient(crate::Loss, super::Value, super::Value),
+}
+
+impl Node {
+    pub fn operands(&self) -> Vec<&super::Value> {
+        match self {
+            Node::Add(a, b)
+            | Node::Subtract(a, b)
+            | Node::Divide(a, b)
+            | Node::Multiply(a, b)
+            | Node::HadamardProduct(a, b)
+            | Node::Loss(_, a, b)
+            | Node::LossGradient(_, a, b)
+            | Node::Concat(a, b, _) => vec![a, b],
+            Node::Convolve(a, _, _)
+            | Node::ConvergeSum(a)
+            | Node::Sigmoid(a)
+            | Node::Tanh(a)
+            | Node::Relu(a)
+            | Node::LeakyRelu(a, _)
+            | Node::Elu(a)
+            | Node::Swish(a)
+            | Node::Softplus(a, _)
+            | Node::Transpose(a)
+            | Node::Sum(a)
+            | Node::Broadcast(a)
+            | Node::Accumulate(a)
+            | Node::Spread(a)
+            | Node::Deconvolve(a, _, _)
+            | Node::Derivative(_, a)
+            | Node::Reshape(a)
+            | Node::Pad(a, _)
+            | Node::Crop(a, _)
+            | Node::Permute(a, _)
+            | Node::Slice(a, _, _) => vec![a],
+        }
+    }
+
+    /// Same operation over `operands`, given in the order of `operands()`
+    pub fn with_operands(&self, operands: Vec<super::Value>) -> Node {
+        let mut operands = operands.into_iter();
+        let mut next = move || operands.next().expect("Missing operand");
+
+        match self {
+            Node::Add(..) => Node::Add(next(), next()),
+            Node::Subtract(..) => Node::Subtract(next(), next()),
+            Node::Divide(..) => Node::Divide(next(), next()),
+            Node::Multiply(..) => Node::Multiply(next(), next()),
+            Node::HadamardProduct(..) => Node::HadamardProduct(next(), next()),
+            Node::Convolve(_, size, stride) => Node::Convolve(next(), *size, *stride),
+            Node::ConvergeSum(..) => Node::ConvergeSum(next()),
+            Node::Sigmoid(..) => Node::Sigmoid(next()),
+            Node::Tanh(..) => Node::Tanh(next()),
+            Node::Relu(..) => Node::Relu(next()),
+            Node::LeakyRelu(_, beta) => Node::LeakyRelu(next(), *beta),
+            Node::Elu(..) => Node::Elu(next()),
+            Node::Swish(..) => Node::Swish(next()),
+            Node::Softplus(_, beta) => Node::Softplus(next(), *beta),
+            Node::Transpose(..) => Node::Transpose(next()),
+            Node::Sum(..) => Node::Sum(next()),
+            Node::Broadcast(..) => Node::Broadcast(next()),
+            Node::Accumulate(..) => Node::Accumulate(next()),
+            Node::Spread(..) => Node::Spread(next()),
+            Node::Deconvolve(_, size, stride) => Node::Deconvolve(next(), *size, *stride),
+            Node::Derivative(activation_fn, _) => Node::Derivative(*activation_fn, next()),
+            Node::Reshape(..) => Node::Reshape(next()),
+            Node::Pad(_, padding) => Node::Pad(next(), *padding),
+            Node::Crop(_, padding) => Node::Crop(next(), *padding),
+            Node::Permute(_, axes) => Node::Permute(next(), axes.clone()),
+            Node::Concat(_, _, axis) => Node::Concat(next(), next(), *axis),
+            Node::Slice(_, axis, start) => Node::Slice(next(), *axis, *start),
+            Node::Loss(loss, _, _) => Node::Loss(*loss, next(), next()),
+            Node::LossGradient(loss, _, _) => Node::LossGradient(*loss, next(), next()),
+        }
+    }
+}
@@ -1,6 +1,20 @@
-#[derive(PartialEq)]
+use std::rc::Rc;
+
+#[derive(Clone)]
 pub enum Operand {
     Parameter(u32, super::GeneralType),
     Constant(super::Constant),
-    Node(Box<super::Node>),
+    Node(Rc<super::Node>),
+}
+
+impl PartialEq for Operand {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Operand::Parameter(a, at), Operand::Parameter(b, bt)) => a == b && at == bt,
+            (Operand::Constant(a), Operand::Constant(b)) => a == b,
+            // Shared nodes are equal without walking them
+            (Operand::Node(a), Operand::Node(b)) => Rc::ptr_eq(a, b) || a == b,
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::{ Value, Operand };
+
+/// Rewrites a graph into one computing the same outputs, each of the same
+/// type, batch and windows as before
+pub(crate) trait Pass {
+    fn run(&self, outputs: &[&Value]) -> crate::Result<Vec<Value>>;
+}
+
+/// Passes run one after the other over a graph before it is lowered
+pub(crate) struct PassManager {
+    passes: Vec<Box<dyn Pass>>,
+}
+
+impl PassManager {
+    pub fn new() -> Self {
+        Self {
+            passes: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, pass: impl Pass + 'static) {
+        self.passes.push(Box::new(pass));
+    }
+
+    pub fn run(&self, outputs: &[&Value]) -> crate::Result<Vec<Value>> {
+        let mut outputs: Vec<Value> = outputs.iter().map(|output| (*output).clone()).collect();
+
+        for pass in &self.passes {
+            outputs = pass.run(&outputs.iter().collect::<Vec<_>>())?;
+        }
+
+        Ok(outputs)
+    }
+}
+
+impl Default for PassManager {
+    /// Folds constants first, so that the identities simplified next show,
+    /// then merges whatever computes the same thing twice
+    fn default() -> Self {
+        let mut manager = Self::new();
+        manager.add(super::passes::ConstantFolding);
+        manager.add(super::passes::Simplification);
+        manager.add(super::passes::CommonSubexpressions);
+        manager
+    }
+}
+
+/// Rebuilds the graph of `outputs` from its parameters and constants up,
+/// handing every node to `rewrite` once its operands have been rewritten.
+/// Nodes shared by several values are rewritten once and stay shared.
+pub(super) fn rewrite(outputs: &[&Value], rewrite: &mut dyn FnMut(Value) -> crate::Result<Value>) -> crate::Result<Vec<Value>> {
+    let mut rewritten: HashMap<usize, Value> = HashMap::new();
+
+    let lookup = |rewritten: &HashMap<usize, Value>, value: &Value| {
+        value.id()
+            .and_then(|id| rewritten.get(&id).cloned())
+            .unwrap_or_else(|| value.clone())
+    };
+
+    for value in Value::postorder(outputs) {
+        let (node, id) = match (&value.inner, value.id()) {
+            (Operand::Node(node), Some(id)) => (node, id),
+            _ => continue,
+        };
+
+        let operands: Vec<Value> = node.operands()
+            .into_iter()
+            .map(|operand| lookup(&rewritten, operand))
+            .collect();
+
+        let unchanged = operands.iter()
+            .zip(node.operands())
+            .all(|(new, old)| new.id() == old.id());
+
+        let value = if unchanged {
+            value.clone()
+        } else {
+            Value {
+                inner: Operand::Node(Rc::new(node.with_operands(operands))),
+                ..value.clone()
+            }
+        };
+
+        rewritten.insert(id, rewrite(value)?);
+    }
+
+    Ok(outputs.iter().map(|output| lookup(&rewritten, output)).collect())
+}
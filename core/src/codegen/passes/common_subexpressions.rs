@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+use std::mem::Discriminant;
+
+use crate::codegen::{ Value, Operand, Node };
+use crate::codegen::pass::{ Pass, rewrite };
+
+/// Operation of a node and the nodes it reads, parameters and constants
+/// having no id
+type Key = (Discriminant<Node>, Vec<Option<usize>>);
+
+/// Merges nodes computing the same operation over the same operands, so
+/// that they are evaluated once
+pub(crate) struct CommonSubexpressions;
+
+impl Pass for CommonSubexpressions {
+    fn run(&self, outputs: &[&Value]) -> crate::Result<Vec<Value>> {
+        // The operands of every node have been merged by the time it comes up
+        let mut seen: HashMap<Key, Vec<Value>> = HashMap::new();
+
+        rewrite(outputs, &mut |value| {
+            let node = match &value.inner {
+                Operand::Node(node) => node,
+                _ => return Ok(value),
+            };
+
+            let key = (
+                std::mem::discriminant(node.as_ref()),
+                node.operands().iter().map(|operand| operand.id()).collect(),
+            );
+
+            let candidates = seen.entry(key).or_default();
+
+            if let Some(existing) = candidates.iter().find(|candidate| **candidate == value) {
+                return Ok(existing.clone());
+            }
+
+            candidates.push(value.clone());
+            Ok(value)
+        })
+    }
+}
@@ -0,0 +1,102 @@
+use crate::codegen::{ Value, Operand, Node, Constant, Interpreter };
+use crate::codegen::pass::{ Pass, rewrite };
+use crate::codegen::value::Convergent;
+
+/// Evaluates every node reading only constants ahead of time, replacing it
+/// with a constant of its result. Broadcasts are left alone, as they would
+/// only trade a cheap loop for a large constant. So are integer nodes, as
+/// the interpreter folding them computes in `f64` rather than wrapping
+/// like compiled kernels do.
+pub(crate) struct ConstantFolding;
+
+impl Pass for ConstantFolding {
+    fn run(&self, outputs: &[&Value]) -> crate::Result<Vec<Value>> {
+        rewrite(outputs, &mut |value| {
+            let node = match &value.inner {
+                Operand::Node(node) => node,
+                _ => return Ok(value),
+            };
+
+            let constant = node.operands()
+                .iter()
+                .all(|operand| matches!(operand.inner, Operand::Constant(_)) && operand.general_type.scalar_type().is_float());
+
+            if !value.general_type.scalar_type().is_float() {
+                return Ok(value);
+            }
+
+            if !constant || value.batch != 1 || value.convergent != Convergent::None || matches!(node.as_ref(), Node::Broadcast(_)) {
+                return Ok(value);
+            }
+
+            let data = Interpreter::constant(&value)?;
+
+            Ok(Value {
+                inner: Operand::Constant(Constant::from_scalars(value.general_type, &data)?),
+                ..value
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConstantFolding;
+    use crate::codegen::{ Operand, Value };
+    use crate::codegen::pass::Pass;
+    use crate::{ Dimension, Element, Engine, Kernel, Tensor };
+
+    fn folded(value: &Value) -> bool {
+        let result = ConstantFolding.run(&[value]).unwrap();
+        matches!(result[0].inner, Operand::Constant(_))
+    }
+
+    /// `value` evaluated with and without the default passes
+    fn evaluate<F: crate::Scalar>(value: &Value) -> (Element<F>, Element<F>) {
+        let engine = Engine::new().unwrap();
+        let unused = Tensor::<F>::zeros(Dimension::new(1, 1, 1), 1).unwrap();
+
+        let optimized: Kernel<Tensor<F>, Element<F>, Tensor<F>> = engine.compile(value).unwrap();
+        let unoptimized: Kernel<Tensor<F>, Element<F>, Tensor<F>> = engine.interpret(value).unwrap();
+
+        (optimized.run(&unused, &unused).unwrap(), unoptimized.run(&unused, &unused).unwrap())
+    }
+
+    #[test]
+    fn folds_float_nodes() {
+        let tensor = Tensor::from_vec(vec![0.5f64, -1.25, 3.0, 2.0], Dimension::new(2, 2, 1), 1).unwrap();
+        let value = Value::from(tensor.clone())
+            .multiply(Value::from(tensor)).unwrap()
+            .multiply(1.5f64).unwrap()
+            .tanh().unwrap()
+            .sum().unwrap();
+
+        assert!(folded(&value));
+
+        // Transcendental functions may differ in their last bits
+        let (optimized, unoptimized) = evaluate::<f64>(&value);
+        assert!((optimized[0] - unoptimized[0]).abs() < 1e-12, "{} != {}", optimized, unoptimized);
+
+        let single = Value::from(0.1f32).add(0.2f32).unwrap().divide(3.0f32).unwrap();
+        let (optimized, unoptimized) = evaluate::<f32>(&single);
+        assert_eq!(optimized, unoptimized);
+        assert_eq!(optimized[0], (0.1f32 + 0.2) / 3.0);
+    }
+
+    #[test]
+    fn leaves_integer_nodes() {
+        assert!(!folded(&Value::from(200u8).add(100u8).unwrap()));
+        assert!(!folded(&Value::from(1u32).subtract(2u32).unwrap()));
+        assert!(!folded(&Value::from(7i16).divide(0i16).unwrap()));
+        assert!(!folded(&Value::from(i64::MAX).subtract(1i64).unwrap()));
+    }
+
+    #[cfg(feature = "llvm")]
+    #[test]
+    fn integers_wrap_like_compiled_kernels() {
+        assert_eq!(evaluate::<u8>(&Value::from(200u8).add(100u8).unwrap()).0[0], 44);
+        assert_eq!(evaluate::<u32>(&Value::from(1u32).subtract(2u32).unwrap()).0[0], u32::MAX);
+        assert_eq!(evaluate::<i64>(&Value::from(i64::MAX - 2).add(1i64).unwrap()).0[0], i64::MAX - 1);
+        assert_eq!(evaluate::<u64>(&Value::from(u64::MAX).divide(3u64).unwrap()).0[0], u64::MAX / 3);
+    }
+}
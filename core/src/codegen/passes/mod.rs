@@ -0,0 +1,7 @@
+mod constant_folding;
+mod simplification;
+mod common_subexpressions;
+
+pub(crate) use constant_folding::ConstantFolding;
+pub(crate) use simplification::Simplification;
+pub(crate) use common_subexpressions::CommonSubexpressions;
@@ -0,0 +1,84 @@
+use crate::codegen::{ Value, Operand, Node, GeneralType };
+use crate::codegen::pass::{ Pass, rewrite };
+
+/// Removes operations that hand back their operand unchanged, such as
+/// `x * 1`, `x + 0`, `relu(relu(x))` or a reshape to the same type
+pub(crate) struct Simplification;
+
+impl Pass for Simplification {
+    fn run(&self, outputs: &[&Value]) -> crate::Result<Vec<Value>> {
+        rewrite(outputs, &mut |value| Ok(simplify(value)))
+    }
+}
+
+fn simplify(value: Value) -> Value {
+    let node = match &value.inner {
+        Operand::Node(node) => node.clone(),
+        _ => return value,
+    };
+
+    let replacement = match node.as_ref() {
+        Node::Add(a, b) => match (filled(a), filled(b)) {
+            (_, Some(0.0)) => Some(a.clone()),
+            (Some(0.0), _) => Some(b.clone()),
+            _ => None,
+        },
+        Node::Subtract(a, b) if filled(b) == Some(0.0) => Some(a.clone()),
+        Node::HadamardProduct(a, b) => match (filled(a), filled(b)) {
+            (_, Some(1.0)) => Some(a.clone()),
+            (Some(1.0), _) => Some(b.clone()),
+            _ => None,
+        },
+        // A tensor of ones times a tensor is a matrix product, not the tensor
+        Node::Multiply(a, b) => match (filled(a), filled(b)) {
+            (_, Some(1.0)) if matches!(b.general_type, GeneralType::Element(_)) => Some(a.clone()),
+            (Some(1.0), _) if matches!(a.general_type, GeneralType::Element(_)) => Some(b.clone()),
+            _ => None,
+        },
+        Node::Divide(a, b) if filled(b) == Some(1.0) => Some(a.clone()),
+        Node::Relu(a) if is_relu(a) => Some(a.clone()),
+        Node::Transpose(a) => match &a.inner {
+            Operand::Node(inner) => match inner.as_ref() {
+                Node::Transpose(x) => Some(x.clone()),
+                _ => None,
+            },
+            _ => None,
+        },
+        Node::Reshape(a) | Node::Broadcast(a) => Some(a.clone()),
+        _ => None,
+    };
+
+    // Operands broadcast to a larger type have to stay where they are
+    match replacement {
+        Some(replacement) if same_type(&replacement, &value) => replacement,
+        _ => value,
+    }
+}
+
+/// Scalar every scalar of `value` is known to be equal to
+fn filled(value: &Value) -> Option<f64> {
+    match &value.inner {
+        Operand::Constant(constant) => {
+            let scalars = constant.scalars();
+            let first = *scalars.first()?;
+
+            scalars.iter().all(|scalar| *scalar == first).then_some(first)
+        },
+        Operand::Node(node) => match node.as_ref() {
+            Node::Broadcast(operand) => filled(operand),
+            _ => None,
+        },
+        Operand::Parameter(..) => None,
+    }
+}
+
+fn is_relu(value: &Value) -> bool {
+    match &value.inner {
+        Operand::Node(node) => matches!(node.as_ref(), Node::Relu(_)),
+        _ => false,
+    }
+}
+
+fn same_type(a: &Value, b: &Value) -> bool {
+    a.general_type == b.general_type && a.batch == b.batch && a.convergent == b.convergent
+}
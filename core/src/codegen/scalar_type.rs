@@ -12,3 +12,62 @@ pub enum ScalarType {
     I32,
     I64
 }
+
+impl ScalarType {
+    pub fn is_float(&self) -> bool {
+        matches!(self, ScalarType::F32 | ScalarType::F64)
+    }
+
+    pub fn is_signed(&self) -> bool {
+        matches!(self, ScalarType::I8 | ScalarType::I16 | ScalarType::I32 | ScalarType::I64)
+    }
+
+    /// Size of a scalar in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            ScalarType::U8 | ScalarType::I8 => 1,
+            ScalarType::U16 | ScalarType::I16 => 2,
+            ScalarType::F32 | ScalarType::U32 | ScalarType::I32 => 4,
+            ScalarType::F64 | ScalarType::U64 | ScalarType::I64 => 8,
+        }
+    }
+
+    /// Reads the scalar at `index` of a buffer of this type
+    ///
+    /// # Safety
+    /// `pointer` must be valid for reads of `index + 1` scalars of this type
+    pub unsafe fn read(&self, pointer: *const u8, index: usize) -> f64 {
+        match self {
+            ScalarType::F32 => *(pointer as *const f32).add(index) as f64,
+            ScalarType::F64 => *(pointer as *const f64).add(index),
+            ScalarType::U8 => *pointer.add(index) as f64,
+            ScalarType::U16 => *(pointer as *const u16).add(index) as f64,
+            ScalarType::U32 => *(pointer as *const u32).add(index) as f64,
+            ScalarType::U64 => *(pointer as *const u64).add(index) as f64,
+            ScalarType::I8 => *(pointer as *const i8).add(index) as f64,
+            ScalarType::I16 => *(pointer as *const i16).add(index) as f64,
+            ScalarType::I32 => *(pointer as *const i32).add(index) as f64,
+            ScalarType::I64 => *(pointer as *const i64).add(index) as f64,
+        }
+    }
+
+    /// Writes `value` to `index` of a buffer of this type, converting it with
+    /// `as` semantics
+    ///
+    /// # Safety
+    /// `pointer` must be valid for writes of `index + 1` scalars of this type
+    pub unsafe fn write(&self, pointer: *mut u8, index: usize, value: f64) {
+        match self {
+            ScalarType::F32 => *(pointer as *mut f32).add(index) = value as f32,
+            ScalarType::F64 => *(pointer as *mut f64).add(index) = value,
+            ScalarType::U8 => *pointer.add(index) = value as u8,
+            ScalarType::U16 => *(pointer as *mut u16).add(index) = value as u16,
+            ScalarType::U32 => *(pointer as *mut u32).add(index) = value as u32,
+            ScalarType::U64 => *(pointer as *mut u64).add(index) = value as u64,
+            ScalarType::I8 => *(pointer as *mut i8).add(index) = value as i8,
+            ScalarType::I16 => *(pointer as *mut i16).add(index) = value as i16,
+            ScalarType::I32 => *(pointer as *mut i32).add(index) = value as i32,
+            ScalarType::I64 => *(pointer as *mut i64).add(index) = value as i64,
+        }
+    }
+}
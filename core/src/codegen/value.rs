@@ -1,3 +1,6 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
 use super::{
     Operand,
     Constant,
@@ -9,39 +12,171 @@ use super::{
 
 use crate::Errors;
 
+/// Handle to a value of a graph. Clones are cheap and share the node they
+/// refer to, so a value can be read by any number of others while backends
+/// still evaluate it once.
+#[derive(Clone)]
+#[derive(PartialEq)]
 pub struct Value {
-    convergent: Convergent,
-    inner: Operand,
-    general_type: GeneralType,
+    pub(super) convergent: Convergent,
+    pub(super) inner: Operand,
+    pub(super) general_type: GeneralType,
+    pub(super) batch: u32,
 }
 
+#[derive(Clone, Copy)]
 #[derive(PartialEq)]
-enum Convergent {
+pub(super) enum Convergent {
     None,
     Convolve((u32, u32, u32), (u32, u32), (u32, u32)),
 }
 
+impl Convergent {
+    fn merge(self, other: Self) -> crate::Result<Self> {
+        match (self, other) {
+            (Convergent::None, other) => Ok(other),
+            (this, Convergent::None) => Ok(this),
+            (this, other) => if this == other {
+                Ok(this)
+            } else {
+                Errors::UnableToConvergeOperand.into()
+            }
+        }
+    }
+
+    /// Number of convolution windows a convergent value is evaluated over
+    pub(super) fn positions(&self) -> u32 {
+        match self {
+            Convergent::None => 1,
+            Convergent::Convolve(input, filter, stride) => {
+                let x = (input.0 - filter.0) / stride.0 + 1;
+                let y = (input.1 - filter.1) / stride.1 + 1;
+                x * y
+            }
+        }
+    }
+}
+
 impl Value {
+    /// Number of samples this value holds, every operation being applied to
+    /// each of them separately. `general_type` is the type of one sample.
+    pub fn batch(&self) -> u32 {
+        self.batch
+    }
+
+    /// Type of the buffer holding every sample of this value
+    pub(crate) fn buffer_type(&self) -> crate::Result<GeneralType> {
+        self.general_type.batched(self.batch)
+    }
+
+    /// Whether this value is still evaluated per convolution window
+    pub(crate) fn is_convergent(&self) -> bool {
+        self.convergent != Convergent::None
+    }
+
+    /// Placeholder for kernel parameter `index`, read from a buffer of
+    /// `general_type`. Kernels take their parameters as the input followed
+    /// by the trainables, so layers number them the same way.
+    pub fn input(index: u32, general_type: GeneralType) -> Self {
+        Self {
+            inner: Operand::Parameter(index, general_type),
+            general_type,
+            convergent: Convergent::None,
+            batch: 1,
+        }
+    }
+
+    /// Placeholder for kernel parameter `index` holding `batch` samples of
+    /// `general_type`, read from a buffer of `general_type.batched(batch)`
+    pub fn batched_input(index: u32, general_type: GeneralType, batch: u32) -> crate::Result<Self> {
+        if batch == 0 {
+            return Errors::InvalidBatchSize.into();
+        }
+
+        Ok(Self {
+            inner: Operand::Parameter(index, general_type.batched(batch)?),
+            general_type,
+            convergent: Convergent::None,
+            batch,
+        })
+    }
+
+    /// Records the type of every parameter read by this value at its index,
+    /// failing if one index is read as two different types
+    pub(crate) fn parameters(&self, parameters: &mut Vec<Option<GeneralType>>) -> crate::Result<()> {
+        for value in Value::postorder(&[self]) {
+            if let Operand::Parameter(index, general_type) = value.inner {
+                let index = index as usize;
+
+                if parameters.len() <= index {
+                    parameters.resize(index + 1, None);
+                }
+
+                match parameters[index] {
+                    Some(other) if other != general_type => return Errors::DifferentParameterTypes.into(),
+                    _ => parameters[index] = Some(general_type),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Identity of the node this value is the result of, shared by all of
+    /// its clones. Parameters and constants have none.
+    pub(super) fn id(&self) -> Option<usize> {
+        match &self.inner {
+            Operand::Node(node) => Some(Rc::as_ptr(node) as usize),
+            _ => None,
+        }
+    }
+
+    /// Every value `outputs` are built from, each after its operands. Nodes
+    /// shared by several values appear once, parameters and constants every
+    /// time they are read.
+    pub(super) fn postorder(outputs: &[&Value]) -> Vec<Value> {
+        fn visit(value: &Value, visited: &mut HashSet<usize>, order: &mut Vec<Value>) {
+            if let Operand::Node(node) = &value.inner {
+                if !visited.insert(Rc::as_ptr(node) as usize) {
+                    return;
+                }
+
+                for operand in node.operands() {
+                    visit(operand, visited, order);
+                }
+            }
+
+            order.push(value.clone());
+        }
+
+        let mut visited = HashSet::new();
+        let mut order = Vec::new();
+
+        for output in outputs {
+            visit(output, &mut visited, &mut order);
+        }
+
+        order
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn add(self, operand: impl Into<Self>) -> crate::Result<Self> {
         let operand = operand.into();
+        let convergent = self.convergent.merge(operand.convergent)?;
+        let batch = merge_batch(self.batch, operand.batch)?;
         
         // Check operand types
-        match (self.general_type, operand.general_type) {
-            (GeneralType::Tensor(ax, ay, az, at), GeneralType::Tensor(bx, by, bz, bt)) => {
+        let general_type = match (self.general_type, operand.general_type) {
+            (GeneralType::Tensor(a, at), GeneralType::Tensor(b, bt)) => {
                 if at != bt {
                     return Errors::DifferentOperandTypes.into();
                 }
     
-                if ax != bx || ay != by || az != bz {
+                if a != b {
                     return Errors::DifferentOperandDimensions.into();
                 }
 
-                Ok(Self {
-                    inner: Operand::Node(Box::new(Node::Add(self.inner, operand.inner))),
-                    general_type: GeneralType::Tensor(ax, ay, az, at),
-                    convergent: self.convergent
-                })
+                GeneralType::Tensor(a, at)
             },
             (GeneralType::Element(ElementType(an, at)), GeneralType::Element(ElementType(bn, bt))) => {
                 if at != bt {
@@ -52,35 +187,36 @@ impl Value {
                     return Errors::DifferentOperandDimensions.into();
                 }
 
-                Ok(Self {
-                    inner: Operand::Node(Box::new(Node::Add(self.inner, operand.inner))),
-                    general_type: self.general_type,
-                    convergent: self.convergent
-                })
+                self.general_type
             },
-            _ => { Errors::InvalidOperandTypes.into() }
-        }
+            _ => { return Errors::InvalidOperandTypes.into() }
+        };
+
+        Ok(Self {
+            inner: Operand::Node(Rc::new(Node::Add(self, operand))),
+            general_type,
+            convergent,
+            batch,
+        })
     }
 
     pub fn subtract(self, operand: impl Into<Self>) -> crate::Result<Self> {
         let operand = operand.into();
+        let convergent = self.convergent.merge(operand.convergent)?;
+        let batch = merge_batch(self.batch, operand.batch)?;
         
         // Check operand types
-        match (self.general_type, operand.general_type) {
-            (GeneralType::Tensor(ax, ay, az, at), GeneralType::Tensor(bx, by, bz, bt)) => {
+        let general_type = match (self.general_type, operand.general_type) {
+            (GeneralType::Tensor(a, at), GeneralType::Tensor(b, bt)) => {
                 if at != bt {
                     return Errors::DifferentOperandTypes.into();
                 }
     
-                if ax != bx || ay != by || az != bz {
+                if a != b {
                     return Errors::DifferentOperandDimensions.into();
                 }
 
-                Ok(Self {
-                    inner: Operand::Node(Box::new(Node::Add(self.inner, operand.inner))),
-                    general_type: GeneralType::Tensor(ax, ay, az, at),
-                    convergent: self.convergent
-                })
+                GeneralType::Tensor(a, at)
             },
             (GeneralType::Element(ElementType(an, at)), GeneralType::Element(ElementType(bn, bt))) => {
                 if at != bt {
@@ -91,39 +227,45 @@ impl Value {
                     return Errors::DifferentOperandDimensions.into();
                 }
 
-                Ok(Self {
-                    inner: Operand::Node(Box::new(Node::Subtract(self.inner, operand.inner))),
-                    general_type: self.general_type,
-                    convergent: self.convergent
-                })
+                self.general_type
             },
-            _ => { Errors::InvalidOperandTypes.into() }
-        }
+            _ => { return Errors::InvalidOperandTypes.into() }
+        };
+
+        Ok(Self {
+            inner: Operand::Node(Rc::new(Node::Subtract(self, operand))),
+            general_type,
+            convergent,
+            batch,
+        })
     }
     
     pub fn multiply(self, operand: impl Into<Self>) -> crate::Result<Self> {
         let operand = operand.into();
+        let convergent = self.convergent.merge(operand.convergent)?;
+        let batch = merge_batch(self.batch, operand.batch)?;
 
         let general_type = match (self.general_type, operand.general_type) {
-            (GeneralType::Tensor(ax, ay, az, at), GeneralType::Tensor(bx, by, bz, bt)) => {
+            (GeneralType::Tensor(a, at), GeneralType::Tensor(b, bt)) => {
                 if at != bt {
                     return Errors::DifferentOperandTypes.into();
                 }
-                
-                if ay != bx || az != bz {
+
+                // Axes from the third on are planes multiplied separately
+                if a.y() != b.x() || planes(&a) != planes(&b) {
                     return Errors::IncompatibleOperandDimensions.into();
                 }
 
-                GeneralType::Tensor(ay, bx, bz, at)
+                GeneralType::Tensor(a.with_axis(1, b.y())?, at)
             },
-            (GeneralType::Tensor(_, _, _, at), GeneralType::Element(bt)) => {
+            (GeneralType::Tensor(_, at), GeneralType::Element(bt)) => {
                 if at != bt {
                     return Errors::DifferentOperandTypes.into();
                 }
 
                 self.general_type
             },
-            (GeneralType::Element(at), GeneralType::Tensor(_, _, _, bt)) => {
+            (GeneralType::Element(at), GeneralType::Tensor(_, bt)) => {
                 if at != bt {
                     return Errors::DifferentOperandTypes.into();
                 }
@@ -140,47 +282,55 @@ impl Value {
         };
 
         Ok(Self {
-            inner: Operand::Node(Box::new(Node::Multiply(self.inner, operand.inner))),
+            inner: Operand::Node(Rc::new(Node::Multiply(self, operand))),
             general_type,
-            convergent: self.convergent
+            convergent,
+            batch,
         })
     }
 
     pub fn hadamard_product(self, operand: impl Into<Self>) -> crate::Result<Self> {
         let operand = operand.into();
+        let convergent = self.convergent.merge(operand.convergent)?;
+        let batch = merge_batch(self.batch, operand.batch)?;
 
         // Start checking operands
-        match (self.general_type, operand.general_type) {
-            (GeneralType::Tensor(ax, ay, az, at), GeneralType::Tensor(bx, by, bz, bt)) => {
+        let general_type = match (self.general_type, operand.general_type) {
+            (GeneralType::Tensor(a, at), GeneralType::Tensor(b, bt)) => {
                 if at != bt {
                     return Errors::DifferentOperandTypes.into();
                 }
     
-                if ax != bx || ay != by || az != bz {
+                if a != b {
                     return Errors::DifferentOperandDimensions.into();
                 }
 
-                Ok(Self {
-                    inner: Operand::Node(Box::new(Node::HadamardProduct(self.inner, operand.inner))), 
-                    general_type: self.general_type,
-                    convergent: self.convergent
-                })
+                self.general_type
             },
-            _ => { Errors::InvalidOperandTypes.into() }
-        }
+            _ => { return Errors::InvalidOperandTypes.into() }
+        };
+
+        Ok(Self {
+            inner: Operand::Node(Rc::new(Node::HadamardProduct(self, operand))), 
+            general_type,
+            convergent,
+            batch,
+        })
     }
 
     pub fn divide(self, operand: impl Into<Self>) -> crate::Result<Self> {
         let operand = operand.into();
+        let convergent = self.convergent.merge(operand.convergent)?;
+        let batch = merge_batch(self.batch, operand.batch)?;
 
         let general_type = match (self.general_type, operand.general_type) {
-            (GeneralType::Tensor(_, _, _, _), GeneralType::Tensor(_, _, _, _)) => {
+            (GeneralType::Tensor(..), GeneralType::Tensor(..)) => {
                 return Errors::IncompatibleOperandTypes.into();
             },
-            (GeneralType::Tensor(_, _, _, at), GeneralType::Element(bt)) => if at != bt {
+            (GeneralType::Tensor(_, at), GeneralType::Element(bt)) => if at != bt {
                 return Errors::DifferentOperandTypes.into();
             } else { self.general_type },
-            (GeneralType::Element(at), GeneralType::Tensor(_, _, _, bt)) => if at != bt {
+            (GeneralType::Element(at), GeneralType::Tensor(_, bt)) => if at != bt {
                 return Errors::DifferentOperandTypes.into();
             } else { operand.general_type },
             (GeneralType::Element(at), GeneralType::Element(bt)) => if at != bt {
@@ -189,23 +339,30 @@ impl Value {
         };
 
         Ok(Self {
-            inner: Operand::Node(Box::new(Node::Divide(self.inner, operand.inner))),
+            inner: Operand::Node(Rc::new(Node::Divide(self, operand))),
             general_type,
-            convergent: self.convergent
+            convergent,
+            batch,
         })
     }
 
-    pub fn convolve(mut self, size: (u32, u32), stride: (u32, u32)) -> crate::Result<Self> {
+    pub fn convolve(self, size: (u32, u32), stride: (u32, u32)) -> crate::Result<Self> {
 
         if self.convergent != Convergent::None {
             return Errors::UnableToConvolve.into();
         }
 
-        let general_type = match self.general_type {
-            GeneralType::Tensor(x, y, z, ty) => {
-                self.convergent = Convergent::Convolve((x, y, z), size, stride);
+        let (general_type, convergent) = match self.general_type {
+            GeneralType::Tensor(dimension, ty) => {
+                let (x, y, z) = (dimension.x(), dimension.y(), dimension.z());
+
+                if size.0 == 0 || size.1 == 0 || stride.0 == 0 || stride.1 == 0
+                    || size.0 > x || size.1 > y {
+                    return Errors::UnableToConvolve.into();
+                }
 
-                GeneralType::Tensor(size.0, size.1, z, ty)
+                let window = dimension.with_axis(0, size.0)?.with_axis(1, size.1)?;
+                (GeneralType::Tensor(window, ty), Convergent::Convolve((x, y, z), size, stride))
             },
             GeneralType::Element(_) => {
                 return Errors::RequiresTensor.into();
@@ -214,31 +371,36 @@ impl Value {
 
 
         Ok(Self {
-            inner: Operand::Node(Box::new(Node::Convolve(self.inner, size, stride))),
             general_type,
-            convergent: self.convergent
+            convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Convolve(self, size, stride))),
         })
     }
 
+    /// Sums each convolution window over its first two axes, placing the
+    /// results of all windows side by side
     pub fn converge_sum(self) -> crate::Result<Self> {
         let general_type = match self.convergent {
             Convergent::None => {
                 return Errors::UnableToConvergeOperand.into();
             },
-            Convergent::Convolve(input, filter, stride)
-                => if let GeneralType::Element(element) = self.general_type {
+            Convergent::Convolve(input, filter, stride) => {
                 let x = (input.0 - filter.0)/ stride.0 + 1;
                 let y = (input.1 - filter.1)/ stride.1 + 1;
-                GeneralType::Tensor(x, y, input.2, element)
-            } else {
-                return Errors::UnableToConvergeOperand.into();
+
+                match self.general_type {
+                    GeneralType::Element(element) => GeneralType::Tensor(crate::Dimension::new(x, y, 1), element),
+                    GeneralType::Tensor(dimension, element) => GeneralType::Tensor(dimension.with_axis(0, x)?.with_axis(1, y)?, element),
+                }
             },
         };
 
         Ok(Self {
-            inner: Operand::Node(Box::new(Node::ConvergeSum(self.inner))),
             general_type,
             convergent: Convergent::None,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::ConvergeSum(self))),
         })
     }
 
@@ -256,59 +418,505 @@ impl Value {
 
     pub fn sigmoid(self) -> crate::Result<Self> {
         Ok(Self {
-            inner: Operand::Node(Box::new(Node::Sigmoid(self.inner))),
             general_type: self.general_type,
-            convergent: self.convergent
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Sigmoid(self))),
         })
     }
 
     pub fn tanh(self) -> crate::Result<Self> {
         Ok(Self {
-            inner: Operand::Node(Box::new(Node::Tanh(self.inner))),
             general_type: self.general_type,
-            convergent: self.convergent
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Tanh(self))),
         })
     }
 
     pub fn relu(self) -> crate::Result<Self> {
         Ok(Self {
-            inner: Operand::Node(Box::new(Node::Relu(self.inner))),
             general_type: self.general_type,
-            convergent: self.convergent
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Relu(self))),
         })
     }
 
     pub fn leaky_relu(self, beta: f32) -> crate::Result<Self> {
         Ok(Self {
-            inner: Operand::Node(Box::new(Node::LeakyRelu(self.inner, beta))),
             general_type: self.general_type,
-            convergent: self.convergent
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::LeakyRelu(self, beta))),
         })
     }
 
     pub fn elu(self) -> crate::Result<Self> {
         Ok(Self {
-            inner: Operand::Node(Box::new(Node::Elu(self.inner))),
             general_type: self.general_type,
-            convergent: self.convergent
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Elu(self))),
         })
     }
 
     pub fn swish(self) -> crate::Result<Self> {
         Ok(Self {
-            inner: Operand::Node(Box::new(Node::Swish(self.inner))),
             general_type: self.general_type,
-            convergent: self.convergent
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Swish(self))),
         })
     }
 
     pub fn softplus(self, beta: f32) -> crate::Result<Self> {
         Ok(Self {
-            inner: Operand::Node(Box::new(Node::Softplus(self.inner, beta ))),
             general_type: self.general_type,
-            convergent: self.convergent
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Softplus(self, beta))),
+        })
+    }
+
+    /// Loss of these predictions against `target`, as a single scalar for
+    /// every sample
+    pub fn loss(self, target: impl Into<Self>, loss: &crate::Loss) -> crate::Result<Self> {
+        let target = target.into();
+        let batch = merge_batch(self.batch, target.batch)?;
+
+        if self.convergent != Convergent::None || target.convergent != Convergent::None {
+            return Errors::UnableToConvergeOperand.into();
+        }
+
+        let scalar = match (self.general_type, target.general_type) {
+            (GeneralType::Tensor(a, at), GeneralType::Tensor(b, bt)) => {
+                if at != bt {
+                    return Errors::DifferentOperandTypes.into();
+                }
+
+                if a != b {
+                    return Errors::DifferentOperandDimensions.into();
+                }
+
+                at.1
+            },
+            (GeneralType::Element(at), GeneralType::Element(bt)) => {
+                if at != bt {
+                    return Errors::DifferentOperandTypes.into();
+                }
+
+                at.1
+            },
+            _ => {
+                return Errors::InvalidOperandTypes.into();
+            }
+        };
+
+        if !scalar.is_float() {
+            return Errors::InvalidOperandTypes.into();
+        }
+
+        Ok(Self {
+            general_type: GeneralType::Element(ElementType(1, scalar)),
+            convergent: Convergent::None,
+            batch,
+            inner: Operand::Node(Rc::new(Node::Loss(*loss, self, target))),
+        })
+    }
+
+    pub fn mean_squared_error(self, target: impl Into<Self>) -> crate::Result<Self> {
+        self.loss(target, &crate::Loss::MeanSquaredError)
+    }
+
+    pub fn mean_absolute_error(self, target: impl Into<Self>) -> crate::Result<Self> {
+        self.loss(target, &crate::Loss::MeanAbsoluteError)
+    }
+
+    pub fn binary_cross_entropy(self, target: impl Into<Self>) -> crate::Result<Self> {
+        self.loss(target, &crate::Loss::BinaryCrossEntropy)
+    }
+
+    pub fn categorical_cross_entropy(self, target: impl Into<Self>) -> crate::Result<Self> {
+        self.loss(target, &crate::Loss::CategoricalCrossEntropy)
+    }
+
+    pub fn huber(self, target: impl Into<Self>, delta: f32) -> crate::Result<Self> {
+        self.loss(target, &crate::Loss::Huber(delta))
+    }
+
+    /// Sums every element of a tensor into a single element
+    pub fn sum(self) -> crate::Result<Self> {
+        let general_type = match self.general_type {
+            GeneralType::Tensor(_, element) => GeneralType::Element(element),
+            GeneralType::Element(_) => {
+                return Errors::RequiresTensor.into();
+            }
+        };
+
+        Ok(Self {
+            general_type,
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Sum(self))),
+        })
+    }
+
+    /// Sums every sample into a single one
+    pub fn batch_sum(self) -> crate::Result<Self> {
+        let convergent = self.convergent;
+        Ok(self.accumulate(1, convergent))
+    }
+
+    /// Swaps the first two axes of a tensor
+    pub fn transpose(self) -> crate::Result<Self> {
+        let general_type = match self.general_type {
+            GeneralType::Tensor(dimension, element) => {
+                GeneralType::Tensor(dimension.with_axis(0, dimension.y())?.with_axis(1, dimension.x())?, element)
+            },
+            GeneralType::Element(_) => {
+                return Errors::RequiresTensor.into();
+            }
+        };
+
+        Ok(Self {
+            general_type,
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Transpose(self))),
         })
     }
+
+    /// Reinterprets a tensor as `dimension`, keeping its elements in order
+    pub fn reshape(self, dimension: crate::Dimension) -> crate::Result<Self> {
+        let general_type = match self.general_type {
+            GeneralType::Tensor(current, element) => {
+                if current.elements() != dimension.elements() {
+                    return Errors::IncompatibleOperandDimensions.into();
+                }
+
+                GeneralType::Tensor(dimension, element)
+            },
+            GeneralType::Element(_) => {
+                return Errors::RequiresTensor.into();
+            }
+        };
+
+        Ok(Self {
+            general_type,
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Reshape(self))),
+        })
+    }
+
+    /// Lays every element of a tensor out along its second axis, the shape
+    /// `Dense` layers take
+    pub fn flatten(self) -> crate::Result<Self> {
+        match self.general_type {
+            GeneralType::Tensor(dimension, _) => self.reshape(crate::Dimension::new(1, dimension.elements() as u32, 1)),
+            GeneralType::Element(_) => Errors::RequiresTensor.into(),
+        }
+    }
+
+    /// Reorders the axes of a tensor, axis `i` of the result being axis
+    /// `axes[i]` of this one
+    pub fn permute(self, axes: &[u32]) -> crate::Result<Self> {
+        let general_type = match self.general_type {
+            GeneralType::Tensor(dimension, element) => GeneralType::Tensor(dimension.permute(axes)?, element),
+            GeneralType::Element(_) => {
+                return Errors::RequiresTensor.into();
+            }
+        };
+
+        Ok(Self {
+            general_type,
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Permute(self, axes.to_vec()))),
+        })
+    }
+
+    /// Joins two tensors along `axis`, their other axes having to match
+    pub fn concat(self, operand: impl Into<Self>, axis: u32) -> crate::Result<Self> {
+        let operand = operand.into();
+
+        if axis as usize >= crate::MAX_RANK {
+            return Errors::InvalidAxis.into();
+        }
+
+        // Both sides are read window by window and sample by sample
+        if self.convergent != operand.convergent {
+            return Errors::UnableToConvergeOperand.into();
+        }
+
+        if self.batch != operand.batch {
+            return Errors::DifferentBatchSizes.into();
+        }
+
+        let general_type = match (self.general_type, operand.general_type) {
+            (GeneralType::Tensor(a, at), GeneralType::Tensor(b, bt)) => {
+                if at != bt {
+                    return Errors::DifferentOperandTypes.into();
+                }
+
+                let rank = a.rank().max(b.rank()).max(axis as usize + 1);
+
+                if (0..rank).any(|i| i != axis as usize && a.axis(i) != b.axis(i)) {
+                    return Errors::IncompatibleOperandDimensions.into();
+                }
+
+                GeneralType::Tensor(a.with_axis(axis as usize, a.axis(axis as usize) + b.axis(axis as usize))?, at)
+            },
+            _ => {
+                return Errors::RequiresTensor.into();
+            }
+        };
+
+        Ok(Self {
+            general_type,
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Concat(self, operand, axis))),
+        })
+    }
+
+    /// Joins tensors of the same dimension along a new `axis`, the axes
+    /// from `axis` on moving one further
+    pub fn stack(values: &[Value], axis: u32) -> crate::Result<Self> {
+        let mut stacked: Option<Value> = None;
+
+        for value in values {
+            let value = match value.general_type {
+                GeneralType::Tensor(dimension, _) => value.clone().reshape(dimension.insert_axis(axis as usize, 1)?)?,
+                GeneralType::Element(_) => {
+                    return Errors::RequiresTensor.into();
+                },
+            };
+
+            stacked = Some(match stacked {
+                Some(stacked) => stacked.concat(value, axis)?,
+                None => value,
+            });
+        }
+
+        stacked.ok_or(Errors::RequiresTensor.into())
+    }
+
+    /// Cuts a tensor along `axis` into consecutive parts of `sizes`, which
+    /// have to add up to the length of the axis
+    pub fn split(self, axis: u32, sizes: &[u32]) -> crate::Result<Vec<Self>> {
+        let length = match self.general_type {
+            GeneralType::Tensor(dimension, _) => *dimension.axes().get(axis as usize)
+                .ok_or(crate::Error::from(Errors::InvalidAxis))?,
+            GeneralType::Element(_) => {
+                return Errors::RequiresTensor.into();
+            }
+        };
+
+        if sizes.iter().map(|size| *size as u64).sum::<u64>() != length as u64 {
+            return Errors::IncompatibleOperandDimensions.into();
+        }
+
+        let mut start = 0;
+        let mut parts = Vec::with_capacity(sizes.len());
+
+        for size in sizes {
+            parts.push(self.clone().slice(axis, start, *size)?);
+            start += size;
+        }
+
+        Ok(parts)
+    }
+
+    /// `length` elements along `axis` starting at `start`
+    pub(super) fn slice(self, axis: u32, start: u32, length: u32) -> crate::Result<Self> {
+        let general_type = match self.general_type {
+            GeneralType::Tensor(dimension, element) => match dimension.axes().get(axis as usize) {
+                Some(size) if start as u64 + length as u64 <= *size as u64 => {
+                    GeneralType::Tensor(dimension.with_axis(axis as usize, length)?, element)
+                },
+                Some(_) => return Errors::IncompatibleOperandDimensions.into(),
+                None => return Errors::InvalidAxis.into(),
+            },
+            GeneralType::Element(_) => {
+                return Errors::RequiresTensor.into();
+            }
+        };
+
+        Ok(Self {
+            general_type,
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Slice(self, axis, start))),
+        })
+    }
+
+    /// Surrounds every plane of a tensor with `padding` zeros along its
+    /// first two axes
+    pub(crate) fn pad(self, padding: (u32, u32)) -> crate::Result<Self> {
+        let general_type = match self.general_type {
+            GeneralType::Tensor(dimension, element) => GeneralType::Tensor(dimension
+                .with_axis(0, dimension.x() + 2 * padding.0)?
+                .with_axis(1, dimension.y() + 2 * padding.1)?, element),
+            GeneralType::Element(_) => {
+                return Errors::RequiresTensor.into();
+            }
+        };
+
+        Ok(Self {
+            general_type,
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Pad(self, padding))),
+        })
+    }
+
+    /// Removes `padding` elements from both ends of the first two axes,
+    /// reversing `pad`
+    pub(super) fn crop(self, padding: (u32, u32)) -> crate::Result<Self> {
+        let general_type = match self.general_type {
+            GeneralType::Tensor(dimension, element) => {
+                if dimension.x() < 2 * padding.0 || dimension.y() < 2 * padding.1 {
+                    return Errors::IncompatibleOperandDimensions.into();
+                }
+
+                GeneralType::Tensor(dimension
+                    .with_axis(0, dimension.x() - 2 * padding.0)?
+                    .with_axis(1, dimension.y() - 2 * padding.1)?, element)
+            },
+            GeneralType::Element(_) => {
+                return Errors::RequiresTensor.into();
+            }
+        };
+
+        Ok(Self {
+            general_type,
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Crop(self, padding))),
+        })
+    }
+
+    /// Repeats an element over every element and channel of `general_type`,
+    /// a windowless value over the windows of `convergent` or a single
+    /// sample over `batch` samples
+    pub(super) fn broadcast(self, general_type: GeneralType, batch: u32, convergent: Convergent) -> crate::Result<Self> {
+        match (self.general_type, general_type) {
+            (GeneralType::Element(ElementType(an, at)), GeneralType::Tensor(_, ElementType(bn, bt)))
+            | (GeneralType::Element(ElementType(an, at)), GeneralType::Element(ElementType(bn, bt))) => {
+                if at != bt || (an != 1 && an != bn) {
+                    return Errors::DifferentOperandTypes.into();
+                }
+            },
+            (GeneralType::Tensor(..), GeneralType::Tensor(..)) => {
+                if self.general_type != general_type {
+                    return Errors::DifferentOperandDimensions.into();
+                }
+            },
+            _ => {
+                return Errors::InvalidOperandTypes.into();
+            }
+        }
+
+        if self.convergent != Convergent::None && self.convergent != convergent {
+            return Errors::UnableToConvergeOperand.into();
+        }
+
+        if self.batch != 1 && self.batch != batch {
+            return Errors::DifferentBatchSizes.into();
+        }
+
+        Ok(Self {
+            general_type,
+            convergent,
+            batch,
+            inner: Operand::Node(Rc::new(Node::Broadcast(self))),
+        })
+    }
+
+    /// Sums the windows a value is evaluated over unless `convergent` keeps
+    /// them, and its samples unless `batch` keeps them, reversing
+    /// `broadcast`
+    pub(super) fn accumulate(self, batch: u32, convergent: Convergent) -> Self {
+        Self {
+            general_type: self.general_type,
+            convergent,
+            batch,
+            inner: Operand::Node(Rc::new(Node::Accumulate(self))),
+        }
+    }
+
+    /// Hands every window of `like` the element of a converged value at the
+    /// window's position, reversing `converge_sum`
+    pub(super) fn spread(self, like: &Value) -> Self {
+        Self {
+            general_type: like.general_type,
+            convergent: like.convergent,
+            batch: like.batch,
+            inner: Operand::Node(Rc::new(Node::Spread(self))),
+        }
+    }
+
+    /// Adds every window back onto the position it was taken from,
+    /// reversing `convolve`
+    pub(super) fn deconvolve(self, input: &Value) -> crate::Result<Self> {
+        let (size, stride) = match self.convergent {
+            Convergent::Convolve(_, size, stride) => (size, stride),
+            Convergent::None => {
+                return Errors::UnableToConvergeOperand.into();
+            }
+        };
+
+        Ok(Self {
+            general_type: input.general_type,
+            convergent: input.convergent,
+            batch: input.batch,
+            inner: Operand::Node(Rc::new(Node::Deconvolve(self, size, stride))),
+        })
+    }
+
+    /// Derivative of `activation_fn` at every element
+    pub(super) fn derivative(self, activation_fn: crate::ActivationFunction) -> Self {
+        Self {
+            general_type: self.general_type,
+            convergent: self.convergent,
+            batch: self.batch,
+            inner: Operand::Node(Rc::new(Node::Derivative(activation_fn, self))),
+        }
+    }
+
+    /// Gradient of `loss` with respect to every scalar of the predictions
+    pub(super) fn loss_gradient(self, target: Value, loss: crate::Loss) -> Self {
+        Self {
+            general_type: self.general_type,
+            convergent: self.convergent,
+            batch: self.batch.max(target.batch),
+            inner: Operand::Node(Rc::new(Node::LossGradient(loss, self, target))),
+        }
+    }
+
+    pub(super) fn scalar(scalar_type: ScalarType, value: f64) -> Self {
+        let constant = match scalar_type {
+            ScalarType::F32 => Constant::ScalarF32(value as f32),
+            ScalarType::F64 => Constant::ScalarF64(value),
+            ScalarType::U8 => Constant::ScalarU8(value as u8),
+            ScalarType::U16 => Constant::ScalarU16(value as u16),
+            ScalarType::U32 => Constant::ScalarU32(value as u32),
+            ScalarType::U64 => Constant::ScalarU64(value as u64),
+            ScalarType::I8 => Constant::ScalarI8(value as i8),
+            ScalarType::I16 => Constant::ScalarI16(value as i16),
+            ScalarType::I32 => Constant::ScalarI32(value as i32),
+            ScalarType::I64 => Constant::ScalarI64(value as i64),
+        };
+
+        Self {
+            inner: Operand::Constant(constant),
+            general_type: GeneralType::Element(ElementType(1, scalar_type)),
+            convergent: Convergent::None,
+            batch: 1,
+        }
+    }
 }
 
 impl From<f32> for Value {
@@ -318,6 +926,7 @@ impl From<f32> for Value {
             inner: Operand::Constant(Constant::ScalarF32(value)),
             general_type: GeneralType::Element(ElementType(1, ScalarType::F32)),
             convergent: Convergent::None,
+            batch: 1,
         }
     }
 }
@@ -329,6 +938,7 @@ impl From<f64> for Value {
             inner: Operand::Constant(Constant::ScalarF64(value)),
             general_type: GeneralType::Element(ElementType(1, ScalarType::F64)),
             convergent: Convergent::None,
+            batch: 1,
         }
     }
 }
@@ -340,6 +950,7 @@ impl From<u8> for Value {
             inner: Operand::Constant(Constant::ScalarU8(value)),
             general_type: GeneralType::Element(ElementType(1, ScalarType::U8)),
             convergent: Convergent::None,
+            batch: 1,
         }
     }
 }
@@ -351,6 +962,7 @@ impl From<u16> for Value {
             inner: Operand::Constant(Constant::ScalarU16(value)),
             general_type: GeneralType::Element(ElementType(1, ScalarType::U16)),
             convergent: Convergent::None,
+            batch: 1,
         }
     }
 }
@@ -362,6 +974,7 @@ impl From<u32> for Value {
             inner: Operand::Constant(Constant::ScalarU32(value)),
             general_type: GeneralType::Element(ElementType(1, ScalarType::U32)),
             convergent: Convergent::None,
+            batch: 1,
         }
     }
 }
@@ -373,6 +986,7 @@ impl From<u64> for Value {
             inner: Operand::Constant(Constant::ScalarU64(value)),
             general_type: GeneralType::Element(ElementType(1, ScalarType::U64)),
             convergent: Convergent::None,
+            batch: 1,
         }
     }
 }
@@ -385,6 +999,7 @@ impl From<i8> for Value {
             inner: Operand::Constant(Constant::ScalarI8(value)),
             general_type: GeneralType::Element(ElementType(1, ScalarType::I8)),
             convergent: Convergent::None,
+            batch: 1,
         }
     }
 }
@@ -396,6 +1011,7 @@ impl From<i16> for Value {
             inner: Operand::Constant(Constant::ScalarI16(value)),
             general_type: GeneralType::Element(ElementType(1, ScalarType::I16)),
             convergent: Convergent::None,
+            batch: 1,
         }
     }
 }
@@ -407,6 +1023,7 @@ impl From<i32> for Value {
             inner: Operand::Constant(Constant::ScalarI32(value)),
             general_type: GeneralType::Element(ElementType(1, ScalarType::I32)),
             convergent: Convergent::None,
+            batch: 1,
         }
     }
 }
@@ -416,8 +1033,103 @@ impl From<i64> for Value {
     fn from(value: i64) -> Self {
         Self {
             inner: Operand::Constant(Constant::ScalarI64(value)),
-            general_type: GeneralType::Element(ElementType(1, ScalarType::I32)),
+            general_type: GeneralType::Element(ElementType(1, ScalarType::I64)),
             convergent: Convergent::None,
+            batch: 1,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Batch size of the result of an operation on values of `a` and `b`
+/// samples, a single sample being repeated over the other operand's
+fn merge_batch(a: u32, b: u32) -> crate::Result<u32> {
+    if a == b || b == 1 {
+        Ok(a)
+    } else if a == 1 {
+        Ok(b)
+    } else {
+        Errors::DifferentBatchSizes.into()
+    }
+}
+
+/// Axes a matrix product runs over separately
+fn planes(dimension: &crate::Dimension) -> &[u32] {
+    dimension.axes().get(2..).unwrap_or(&[])
+}
+
+/// Element and tensor constants of every scalar type. Tensors are embedded
+/// interleaved, the only layout kernels read.
+macro_rules! constant_conversions {
+    ($($scalar:ty => $scalar_type:ident, $element:ident, $tensor:ident;)*) => {
+        $(
+            impl From<crate::Element<$scalar>> for Value {
+                fn from(value: crate::Element<$scalar>) -> Self {
+                    Self {
+                        general_type: GeneralType::Element(ElementType(value.channels() as u32, ScalarType::$scalar_type)),
+                        inner: Operand::Constant(Constant::$element(value)),
+                        convergent: Convergent::None,
+                        batch: 1,
+                    }
+                }
+            }
+
+            impl From<crate::Tensor<$scalar>> for Value {
+                fn from(value: crate::Tensor<$scalar>) -> Self {
+                    let value = value.into_layout(crate::ChannelLayout::Interleaved);
+
+                    Self {
+                        general_type: GeneralType::Tensor(*value.dimension(), ElementType(value.channels() as u32, ScalarType::$scalar_type)),
+                        inner: Operand::Constant(Constant::$tensor(value)),
+                        convergent: Convergent::None,
+                        batch: 1,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+constant_conversions! {
+    f32 => F32, ElementF32, TensorF32;
+    f64 => F64, ElementF64, TensorF64;
+    u8 => U8, ElementU8, TensorU8;
+    u16 => U16, ElementU16, TensorU16;
+    u32 => U32, ElementU32, TensorU32;
+    u64 => U64, ElementU64, TensorU64;
+    i8 => I8, ElementI8, TensorI8;
+    i16 => I16, ElementI16, TensorI16;
+    i32 => I32, ElementI32, TensorI32;
+    i64 => I64, ElementI64, TensorI64;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ ChannelLayout, Dimension, Element, Engine, Graph, Kernel, Tensor };
+
+    #[test]
+    fn planar_constants_are_embedded_interleaved() {
+        let constant = Tensor::from_vec(vec![1u16, 2, 3, 4, 5, 6], Dimension::new(3, 1, 1), 2).unwrap();
+
+        let mut graph = Graph::new();
+        let x = graph.tensor::<u16>(Dimension::new(3, 1, 1), 2);
+        let y = x.add(constant.clone().into_layout(ChannelLayout::Planar)).unwrap();
+
+        let kernel: Kernel<Tensor<u16>, Tensor<u16>, Tensor<u16>> = Engine::new().unwrap().compile(&y).unwrap();
+        let input = Tensor::full(10, Dimension::new(3, 1, 1), 2).unwrap();
+        let unused = Tensor::full(0, Dimension::new(1, 1, 1), 1).unwrap();
+
+        assert_eq!(kernel.run(&input, &unused).unwrap().as_slice(), &[11, 12, 13, 14, 15, 16]);
+    }
+
+    #[test]
+    fn element_constants() {
+        let mut graph = Graph::new();
+        let x = graph.element::<i32>(3);
+        let y = x.multiply(Element::from([2, -1, 0])).unwrap();
+
+        let kernel: Kernel<Element<i32>, Element<i32>, Tensor<i32>> = Engine::new().unwrap().compile(&y).unwrap();
+        let unused = Tensor::full(0, Dimension::new(1, 1, 1), 1).unwrap();
+
+        assert_eq!(kernel.run(&Element::from([4, 5, 6]), &unused).unwrap(), Element::from([8, -5, 0]));
+    }
+}
@@ -1,3 +1,160 @@
-#[derive(Clone, Copy)]
-#[derive(PartialEq, Eq)]
-pub struct Dimension(pub u32, pub u32, pub u32);
\ No newline at end of file
+/// Most axes a `Dimension` can have
+pub const MAX_RANK: usize = 8;
+
+/// Size of every axis of a tensor. Elements along the first axis lie next
+/// to each other in memory, then come those along the second and so on.
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, Hash)]
+pub struct Dimension {
+    axes: [u32; MAX_RANK],
+    rank: u8,
+}
+
+impl Dimension {
+    /// Dimension of `x` rows, `y` columns and `z` planes
+    pub const fn new(x: u32, y: u32, z: u32) -> Self {
+        let mut axes = [1; MAX_RANK];
+        axes[0] = x;
+        axes[1] = y;
+        axes[2] = z;
+
+        Self { axes, rank: 3 }
+    }
+
+    pub fn from_axes(axes: &[u32]) -> crate::Result<Self> {
+        if axes.is_empty() || axes.len() > MAX_RANK {
+            return crate::Errors::InvalidRank.into();
+        }
+
+        let mut dimension = Self {
+            axes: [1; MAX_RANK],
+            rank: axes.len() as u8,
+        };
+
+        dimension.axes[..axes.len()].copy_from_slice(axes);
+        Ok(dimension)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rank as usize
+    }
+
+    pub fn axes(&self) -> &[u32] {
+        &self.axes[..self.rank()]
+    }
+
+    /// Size of `axis`, axes past the rank being of size 1
+    pub fn axis(&self, axis: usize) -> u32 {
+        if axis < self.rank() { self.axes[axis] } else { 1 }
+    }
+
+    pub fn x(&self) -> u32 {
+        self.axis(0)
+    }
+
+    pub fn y(&self) -> u32 {
+        self.axis(1)
+    }
+
+    /// Number of planes, every axis from the third on counting towards it
+    pub fn z(&self) -> u32 {
+        self.axes().iter().skip(2).product()
+    }
+
+    /// Number of elements
+    pub fn elements(&self) -> usize {
+        self.axes().iter().map(|axis| *axis as usize).product()
+    }
+
+    /// Same dimension with `axis` of size `size`, the rank growing to
+    /// include it if needed
+    pub fn with_axis(&self, axis: usize, size: u32) -> crate::Result<Self> {
+        if axis >= MAX_RANK {
+            return crate::Errors::InvalidRank.into();
+        }
+
+        let mut dimension = *self;
+        dimension.axes[axis] = size;
+        dimension.rank = dimension.rank.max(axis as u8 + 1);
+
+        Ok(dimension)
+    }
+
+    /// Dimension with a new axis of `size` at `axis`, the axes from `axis`
+    /// on moving one further
+    pub fn insert_axis(&self, axis: usize, size: u32) -> crate::Result<Self> {
+        if axis > self.rank() || self.rank() == MAX_RANK {
+            return crate::Errors::InvalidRank.into();
+        }
+
+        let mut axes = self.axes().to_vec();
+        axes.insert(axis, size);
+
+        Self::from_axes(&axes)
+    }
+
+    /// Same dimension with its axes reordered, axis `i` becoming axis
+    /// `axes[i]` of this one
+    pub fn permute(&self, axes: &[u32]) -> crate::Result<Self> {
+        let mut sorted = axes.to_vec();
+        sorted.sort();
+
+        if sorted.len() != self.rank() || sorted.iter().enumerate().any(|(i, axis)| *axis as usize != i) {
+            return crate::Errors::InvalidAxis.into();
+        }
+
+        let sizes: Vec<u32> = axes.iter().map(|axis| self.axis(*axis as usize)).collect();
+        Self::from_axes(&sizes)
+    }
+
+    /// Position in storage order of the element at `index`, or `None` if it
+    /// is out of bounds. Axes missing from `index` are taken as 0.
+    pub fn position(&self, index: &Dimension) -> Option<usize> {
+        if index.rank() > self.rank() && index.axes()[self.rank()..].iter().any(|i| *i != 0) {
+            return None;
+        }
+
+        let mut position = 0;
+        let mut stride = 1;
+
+        for (i, size) in self.axes().iter().enumerate() {
+            let coordinate = index.axes().get(i).copied().unwrap_or(0);
+
+            if coordinate >= *size {
+                return None;
+            }
+
+            position += coordinate as usize * stride;
+            stride *= *size as usize;
+        }
+
+        Some(position)
+    }
+
+    /// Index of the element at `position` in storage order
+    pub fn index_at(&self, mut position: usize) -> Dimension {
+        let mut index = *self;
+
+        for i in 0..self.rank() {
+            let size = self.axes[i] as usize;
+            index.axes[i] = (position % size.max(1)) as u32;
+            position /= size.max(1);
+        }
+
+        index
+    }
+
+    /// Every index in storage order
+    pub fn indices(&self) -> impl Iterator<Item = Dimension> {
+        let dimension = *self;
+        (0..self.elements()).map(move |position| dimension.index_at(position))
+    }
+}
+
+impl std::ops::Index<usize> for Dimension {
+    type Output = u32;
+
+    fn index(&self, axis: usize) -> &u32 {
+        &self.axes()[axis]
+    }
+}
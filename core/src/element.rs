@@ -9,21 +9,101 @@ impl<F: Copy> Element<F> {
     pub fn channels(&self) -> usize {
         self.channels
     }
+
+    pub fn as_slice(&self) -> &[F] {
+        unsafe { std::slice::from_raw_parts(self.buffer.as_ptr(), self.channels) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [F] {
+        unsafe { std::slice::from_raw_parts_mut(self.buffer.as_ptr(), self.channels) }
+    }
+
+    /// Element with one channel per scalar of `data`
+    pub fn from_slice(data: &[F]) -> crate::Result<Self> {
+        let buffer = unsafe { Self::allocate(data.len())? };
+
+        unsafe {
+            buffer.as_ptr().copy_from_nonoverlapping(data.as_ptr(), data.len());
+        }
+
+        Ok(Self {
+            channels: data.len(),
+            buffer,
+        })
+    }
+
+    pub fn get(&self, channel: usize) -> Option<&F> {
+        self.as_slice().get(channel)
+    }
+
+    pub fn get_mut(&mut self, channel: usize) -> Option<&mut F> {
+        self.as_mut_slice().get_mut(channel)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, F> {
+        self.as_slice().iter()
+    }
+
+    pub fn to_vec(&self) -> Vec<F> {
+        self.as_slice().to_vec()
+    }
+
+    /// Applies `f` to every channel
+    pub fn map(&self, f: impl Fn(F) -> F) -> Self {
+        let mut result = self.clone();
+        result.as_mut_slice().iter_mut().for_each(|channel| *channel = f(*channel));
+        result
+    }
+
+    /// Combines the channels of both elements pairwise with `f`
+    pub fn zip_with(&self, other: &Self, f: impl Fn(F, F) -> F) -> crate::Result<Self> {
+        if self.channels != other.channels {
+            return crate::Errors::DifferentChannelCounts.into();
+        }
+
+        let mut result = self.clone();
+
+        for (a, b) in result.as_mut_slice().iter_mut().zip(other.as_slice()) {
+            *a = f(*a, *b);
+        }
+
+        Ok(result)
+    }
     
-    unsafe fn allocate(channels: usize) -> crate::Result<NonNull<F>> {
-        use std::alloc::*;
+    pub(crate) fn zeroed(channels: usize) -> crate::Result<Self> {
+        Ok(Self {
+            channels,
+            buffer: {
+                let buffer = unsafe { Self::allocate(channels)? };
+                unsafe { buffer.as_ptr().write_bytes(0, channels); }
+                buffer
+            }
+        })
+    }
 
-        let layout = {
-            let alignment = std::mem::align_of::<F>();
-            let size = std::mem::size_of::<F>() * channels;
+    pub(crate) fn as_ptr(&self) -> *const F {
+        self.buffer.as_ptr()
+    }
 
-            Layout::from_size_align(size, alignment)
-                .or(crate::Errors::ElementAllocationFailed.into())?
-        };
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut F {
+        self.buffer.as_ptr()
+    }
+    
+    unsafe fn allocate(channels: usize) -> crate::Result<NonNull<F>> {
+        let layout = Self::layout(channels)?;
+
+        if layout.size() == 0 {
+            return Ok(NonNull::dangling());
+        }
 
-        NonNull::new(alloc(layout) as _)
+        NonNull::new(std::alloc::alloc(layout) as _)
             .ok_or(crate::Errors::ElementAllocationFailed.into())
     }
+
+    fn layout(channels: usize) -> crate::Result<std::alloc::Layout> {
+        std::alloc::Layout::array::<F>(channels)
+            .or(crate::Errors::ElementAllocationFailed.into())
+    }
 }
 
 impl TryFrom<f32> for Element<f32> {
@@ -241,56 +321,449 @@ impl<F: Copy> TryFrom<(F, F, F, F)> for Element<F> {
 
 impl<F: Copy> Drop for Element<F> {
     fn drop(&mut self) {
-        {
-            let mut i = 0;
+        // The layout was valid when the buffer was allocated
+        let layout = Self::layout(self.channels)
+            .expect("Element layout changed since allocation");
 
-            while i < self.channels {
-                unsafe {
-                    self.buffer.as_ptr()
-                        .add(i)
-                        .drop_in_place();
-                }
-
-                i += 1;
+        if layout.size() != 0 {
+            unsafe {
+                std::alloc::dealloc(self.buffer.as_ptr() as _, layout);
             }
         }
+    }
+}
 
-        use std::alloc::*;
-
-        let layout = unsafe {
-            let alignment = std::mem::align_of::<F>();
-            let size = std::mem::size_of::<F>();
+// The buffer is owned exclusively, like a `Box<[F]>`
+unsafe impl<F: Copy + Send> Send for Element<F> {}
+unsafe impl<F: Copy + Sync> Sync for Element<F> {}
 
-            Layout::from_size_align_unchecked(size, alignment)
-        };
+impl<F: Copy> Clone for Element<F> {
+    fn clone(&self) -> Self {
+        let buffer = unsafe { Self::allocate(self.channels) }
+            .expect("Element allocation failed");
 
         unsafe {
-            dealloc(self.buffer.as_ptr() as _, layout);
+            buffer.as_ptr().copy_from_nonoverlapping(self.buffer.as_ptr(), self.channels);
+        }
+
+        Self {
+            channels: self.channels,
+            buffer,
         }
     }
 }
 
 impl<F: PartialEq + Copy> PartialEq for Element<F> {
     fn eq(&self, other: &Self) -> bool {
-        let mut a = self.buffer;
-        let mut b = other.buffer;
-        let end = unsafe { self.buffer.as_ptr().add(self.channels) };
+        self.channels == other.channels && self.as_slice() == other.as_slice()
+    }
+}
 
-        while a.as_ptr() < end {
-            unsafe {
-                if F::ne(a.as_ref(), b.as_ref()) {
-                    return false;
-                }
+impl<F: crate::Scalar + std::ops::Add<Output = F> + std::ops::Mul<Output = F>> Element<F> {
+    /// Sum of the products of the channels of both elements
+    pub fn dot(&self, other: &Self) -> crate::Result<F> {
+        if self.channels != other.channels {
+            return crate::Errors::DifferentChannelCounts.into();
+        }
+
+        Ok(self.iter()
+            .zip(other.iter())
+            .fold(F::from_f64(0.0), |sum, (a, b)| sum + *a * *b))
+    }
+}
+
+impl<F: Copy> std::ops::Index<usize> for Element<F> {
+    type Output = F;
+
+    fn index(&self, channel: usize) -> &F {
+        &self.as_slice()[channel]
+    }
+}
+
+impl<F: Copy> std::ops::IndexMut<usize> for Element<F> {
+    fn index_mut(&mut self, channel: usize) -> &mut F {
+        &mut self.as_mut_slice()[channel]
+    }
+}
+
+impl<F: Copy + std::ops::Add<Output = F>> std::ops::Add for &Element<F> {
+    type Output = Element<F>;
+
+    /// # Panics
+    /// If the channel counts differ
+    fn add(self, rhs: Self) -> Element<F> {
+        self.zip_with(rhs, |a, b| a + b).expect("Different channel counts")
+    }
+}
+
+impl<F: Copy + std::ops::Sub<Output = F>> std::ops::Sub for &Element<F> {
+    type Output = Element<F>;
+
+    /// # Panics
+    /// If the channel counts differ
+    fn sub(self, rhs: Self) -> Element<F> {
+        self.zip_with(rhs, |a, b| a - b).expect("Different channel counts")
+    }
+}
+
+impl<F: Copy + std::ops::Mul<Output = F>> std::ops::Mul for &Element<F> {
+    type Output = Element<F>;
+
+    /// # Panics
+    /// If the channel counts differ
+    fn mul(self, rhs: Self) -> Element<F> {
+        self.zip_with(rhs, |a, b| a * b).expect("Different channel counts")
+    }
+}
+
+impl<F: Copy + std::ops::Div<Output = F>> std::ops::Div for &Element<F> {
+    type Output = Element<F>;
+
+    /// # Panics
+    /// If the channel counts differ
+    fn div(self, rhs: Self) -> Element<F> {
+        self.zip_with(rhs, |a, b| a / b).expect("Different channel counts")
+    }
+}
+
+impl<F: Copy + std::ops::Add<Output = F>> std::ops::Add for Element<F> {
+    type Output = Element<F>;
+
+    fn add(self, rhs: Self) -> Element<F> {
+        &self + &rhs
+    }
+}
+
+impl<F: Copy + std::ops::Sub<Output = F>> std::ops::Sub for Element<F> {
+    type Output = Element<F>;
+
+    fn sub(self, rhs: Self) -> Element<F> {
+        &self - &rhs
+    }
+}
+
+impl<F: Copy + std::ops::Mul<Output = F>> std::ops::Mul for Element<F> {
+    type Output = Element<F>;
+
+    fn mul(self, rhs: Self) -> Element<F> {
+        &self * &rhs
+    }
+}
+
+impl<F: Copy + std::ops::Div<Output = F>> std::ops::Div for Element<F> {
+    type Output = Element<F>;
+
+    fn div(self, rhs: Self) -> Element<F> {
+        &self / &rhs
+    }
+}
+
+impl<F: crate::Scalar + std::ops::Mul<Output = F>> std::ops::Mul<F> for &Element<F> {
+    type Output = Element<F>;
+
+    fn mul(self, rhs: F) -> Element<F> {
+        self.map(|a| a * rhs)
+    }
+}
+
+impl<F: crate::Scalar + std::ops::Div<Output = F>> std::ops::Div<F> for &Element<F> {
+    type Output = Element<F>;
+
+    fn div(self, rhs: F) -> Element<F> {
+        self.map(|a| a / rhs)
+    }
+}
+
+impl<F: Copy, const N: usize> From<[F; N]> for Element<F> {
+    /// # Panics
+    /// If the allocation fails
+    fn from(value: [F; N]) -> Self {
+        Self::from_slice(&value).expect("Element allocation failed")
+    }
+}
+
+impl<F: Copy, const N: usize> TryFrom<&Element<F>> for [F; N] {
+    type Error = crate::Error;
+
+    fn try_from(value: &Element<F>) -> Result<Self, Self::Error> {
+        value.as_slice()
+            .try_into()
+            .or(crate::Errors::DifferentChannelCounts.into())
+    }
+}
+
+impl<F: Copy> TryFrom<&Element<F>> for (F, F) {
+    type Error = crate::Error;
+
+    fn try_from(value: &Element<F>) -> Result<Self, Self::Error> {
+        match value.as_slice() {
+            [a, b] => Ok((*a, *b)),
+            _ => crate::Errors::DifferentChannelCounts.into(),
+        }
+    }
+}
+
+impl<F: Copy> TryFrom<&Element<F>> for (F, F, F) {
+    type Error = crate::Error;
+
+    fn try_from(value: &Element<F>) -> Result<Self, Self::Error> {
+        match value.as_slice() {
+            [a, b, c] => Ok((*a, *b, *c)),
+            _ => crate::Errors::DifferentChannelCounts.into(),
+        }
+    }
+}
+
+impl<F: Copy> TryFrom<&Element<F>> for (F, F, F, F) {
+    type Error = crate::Error;
+
+    fn try_from(value: &Element<F>) -> Result<Self, Self::Error> {
+        match value.as_slice() {
+            [a, b, c, d] => Ok((*a, *b, *c, *d)),
+            _ => crate::Errors::DifferentChannelCounts.into(),
+        }
+    }
+}
+
+impl<F: Copy + std::fmt::Debug> std::fmt::Debug for Element<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Element").field(&self.as_slice()).finish()
+    }
+}
+
+/// Single channels are shown bare, several as a parenthesized list
+impl<F: Copy + std::fmt::Display> std::fmt::Display for Element<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.channels == 1 {
+            return self.as_slice()[0].fmt(f);
+        }
+
+        f.write_str("(")?;
+
+        for (i, channel) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
             }
 
-            a = unsafe { NonNull::new_unchecked( a.as_ptr().add(1)) };
-            b = unsafe { NonNull::new_unchecked( b.as_ptr().add(1)) };
+            channel.fmt(f)?;
         }
 
-        true
+        f.write_str(")")
     }
 }
 
 pub trait ChannelCount {
     fn channels(&self) -> usize;
-}
\ No newline at end of file
+}
+
+impl ChannelCount for f32 {
+    fn channels(&self) -> usize {
+        1
+    }
+}
+
+impl ChannelCount for f64 {
+    fn channels(&self) -> usize {
+        1
+    }
+}
+
+impl ChannelCount for u8 {
+    fn channels(&self) -> usize {
+        1
+    }
+}
+
+impl ChannelCount for u16 {
+    fn channels(&self) -> usize {
+        1
+    }
+}
+
+impl ChannelCount for u32 {
+    fn channels(&self) -> usize {
+        1
+    }
+}
+
+impl ChannelCount for u64 {
+    fn channels(&self) -> usize {
+        1
+    }
+}
+
+impl ChannelCount for i8 {
+    fn channels(&self) -> usize {
+        1
+    }
+}
+
+impl ChannelCount for i16 {
+    fn channels(&self) -> usize {
+        1
+    }
+}
+
+impl ChannelCount for i32 {
+    fn channels(&self) -> usize {
+        1
+    }
+}
+
+impl ChannelCount for i64 {
+    fn channels(&self) -> usize {
+        1
+    }
+}
+
+impl<F> ChannelCount for (F, F) {
+    fn channels(&self) -> usize {
+        2
+    }
+}
+
+impl<F> ChannelCount for (F, F, F) {
+    fn channels(&self) -> usize {
+        3
+    }
+}
+
+impl<F> ChannelCount for (F, F, F, F) {
+    fn channels(&self) -> usize {
+        4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Element;
+
+    #[test]
+    fn channel_access() {
+        let mut element = Element::from([1.0f32, 2.0, 3.0]);
+
+        assert_eq!(element.channels(), 3);
+        assert_eq!(element.get(1), Some(&2.0));
+        assert_eq!(element.get(3), None);
+
+        element[2] = 5.0;
+        *element.get_mut(0).unwrap() = 4.0;
+        assert_eq!(element.to_vec(), vec![4.0, 2.0, 5.0]);
+        assert_eq!(element.iter().copied().sum::<f32>(), 11.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let element = Element::from([1u8, 2]);
+        let _ = element[2];
+    }
+
+    #[test]
+    fn operators() {
+        let a = Element::from([6i32, 8, 10]);
+        let b = Element::from([3i32, 2, 5]);
+
+        assert_eq!(&a + &b, Element::from([9, 10, 15]));
+        assert_eq!(&a - &b, Element::from([3, 6, 5]));
+        assert_eq!(&a * &b, Element::from([18, 16, 50]));
+        assert_eq!(&a / &b, Element::from([2, 4, 2]));
+        assert_eq!(&a * 2, Element::from([12, 16, 20]));
+        assert_eq!(&a / 2, Element::from([3, 4, 5]));
+        assert_eq!(a + b, Element::from([9, 10, 15]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Different channel counts")]
+    fn operators_need_matching_channels() {
+        let _ = &Element::from([1.0f64, 2.0]) + &Element::from([1.0f64]);
+    }
+
+    #[test]
+    fn dot_product() {
+        let a = Element::from([1.0f64, 2.0, 3.0]);
+        let b = Element::from([4.0f64, -5.0, 6.0]);
+
+        assert_eq!(a.dot(&b).unwrap(), 12.0);
+        assert!(a.dot(&Element::from([1.0, 2.0])).is_err());
+    }
+
+    #[test]
+    fn map_and_zip() {
+        let a = Element::from([1u16, 2, 3]);
+
+        assert_eq!(a.map(|x| x * x), Element::from([1, 4, 9]));
+        assert_eq!(a.zip_with(&Element::from([3, 2, 1]), u16::max).unwrap(), Element::from([3, 2, 3]));
+        assert!(a.zip_with(&Element::from([1, 2]), u16::max).is_err());
+    }
+
+    #[test]
+    fn conversions() {
+        let element: Element<i8> = (1, 2, 3).try_into().unwrap();
+
+        assert_eq!(<(i8, i8, i8)>::try_from(&element).unwrap(), (1, 2, 3));
+        assert_eq!(<[i8; 3]>::try_from(&element).unwrap(), [1, 2, 3]);
+        assert!(<(i8, i8)>::try_from(&element).is_err());
+        assert!(<[i8; 4]>::try_from(&element).is_err());
+
+        let single: Element<f64> = 2.5.try_into().unwrap();
+        assert_eq!(single.to_vec(), vec![2.5]);
+        assert_eq!(Element::from([0u64; 7]).channels(), 7);
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!(format!("{}", Element::from([1.5f32])), "1.5");
+        assert_eq!(format!("{}", Element::from([1u8, 2, 3])), "(1, 2, 3)");
+        assert_eq!(format!("{:?}", Element::from([1u8, 2])), "Element([1, 2])");
+    }
+
+    #[test]
+    fn clones_own_their_buffers() {
+        let original = Element::from([1.0f64, 2.0, 3.0, 4.0]);
+        let mut copy = original.clone();
+
+        assert_eq!(copy, original);
+        assert_ne!(copy.as_slice().as_ptr(), original.as_slice().as_ptr());
+
+        copy[0] = -1.0;
+        assert_eq!(original[0], 1.0);
+        assert_ne!(copy, original);
+
+        drop(original);
+        assert_eq!(copy.to_vec(), vec![-1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn zero_sized_elements() {
+        let empty = Element::<f32>::from_slice(&[]).unwrap();
+        assert_eq!(empty.channels(), 0);
+        assert_eq!(empty.clone(), empty);
+
+        let zeroed = Element::<i64>::zeroed(0).unwrap();
+        assert!(zeroed.as_slice().is_empty());
+
+        let units = Element::from([(); 5]);
+        assert_eq!(units.clone().channels(), 5);
+    }
+
+    #[test]
+    fn equality() {
+        assert_eq!(Element::<u32>::zeroed(3).unwrap(), Element::from([0, 0, 0]));
+        assert_ne!(Element::from([1u32, 2]), Element::from([1, 2, 0]));
+        assert_ne!(Element::from([f64::NAN]), Element::from([f64::NAN]));
+    }
+
+    #[test]
+    fn moves_between_threads() {
+        fn shared<T: Send + Sync>(_: &T) {}
+
+        let element = Element::from([1i16, 2, 3]);
+        shared(&element);
+
+        let copy = element.clone();
+        let total = std::thread::spawn(move || copy.iter().sum::<i16>()).join().unwrap();
+
+        assert_eq!(total, 6);
+    }
+}
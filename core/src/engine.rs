@@ -1,7 +1,186 @@
+#[cfg(feature = "llvm")]
+use std::cell::Cell;
+#[cfg(feature = "llvm")]
+use std::ptr::NonNull;
+#[cfg(feature = "llvm")]
+use std::rc::Rc;
+
+#[cfg(feature = "llvm")]
+use inkwell::OptimizationLevel;
+#[cfg(feature = "llvm")]
+use inkwell::context::Context;
+#[cfg(feature = "llvm")]
+use inkwell::execution_engine::ExecutionEngine;
+#[cfg(feature = "llvm")]
+use inkwell::targets::{ InitializationConfig, Target };
+
+#[cfg(feature = "llvm")]
+use crate::Errors;
+
+/// JIT compiles `Value` graphs into kernels running on the host CPU, or
+/// interprets them when built without the `llvm` feature
 pub struct Engine {
+    #[cfg(feature = "llvm")]
+    jit: Rc<Jit>,
+    #[cfg(feature = "llvm")]
+    kernel_count: Cell<u32>,
+}
+
+/// LLVM state shared between an engine and every kernel it compiled, so
+/// the machine code stays alive for as long as any of them does
+#[cfg(feature = "llvm")]
+pub(crate) struct Jit {
+    // Must be dropped before the context it was created from
+    execution_engine: ExecutionEngine<'static>,
+    context: OwnedContext,
+}
+
+#[cfg(feature = "llvm")]
+struct OwnedContext(NonNull<Context>);
+
+#[cfg(feature = "llvm")]
+impl OwnedContext {
+    fn new() -> Self {
+        Self(NonNull::from(Box::leak(Box::new(Context::create()))))
+    }
+
+    /// The returned reference must not be used after `self` is dropped
+    unsafe fn get(&self) -> &'static Context {
+        &*self.0.as_ptr()
+    }
+}
 
+#[cfg(feature = "llvm")]
+impl Drop for OwnedContext {
+    fn drop(&mut self) {
+        unsafe {
+            drop(Box::from_raw(self.0.as_ptr()));
+        }
+    }
 }
 
 impl Engine {
-    
-}
\ No newline at end of file
+    #[cfg(feature = "llvm")]
+    pub fn new() -> crate::Result<Self> {
+        Target::initialize_native(&InitializationConfig::default())
+            .map_err(|_| crate::Error::from(Errors::EngineCreationFailed))?;
+
+        let context = OwnedContext::new();
+        let execution_engine = unsafe { context.get() }
+            .create_module("neu")
+            .create_jit_execution_engine(OptimizationLevel::Default)
+            .map_err(|_| crate::Error::from(Errors::EngineCreationFailed))?;
+
+        Ok(Self {
+            jit: Rc::new(Jit {
+                execution_engine,
+                context,
+            }),
+            kernel_count: Cell::new(0),
+        })
+    }
+
+    #[cfg(not(feature = "llvm"))]
+    pub fn new() -> crate::Result<Self> {
+        Ok(Self {})
+    }
+
+    /// Optimises `value` and compiles it into a kernel writing it to its
+    /// output
+    pub fn compile<I, O, T>(&self, value: &crate::Value) -> crate::Result<crate::Kernel<I, O, T>>
+        where I: crate::LayerValue, O: crate::LayerValue, T: crate::LayerTrainables {
+        self.compile_outputs(&[value])
+    }
+
+    /// Builds a kernel evaluating `value` with the reference interpreter
+    /// instead of compiling it
+    pub fn interpret<I, O, T>(&self, value: &crate::Value) -> crate::Result<crate::Kernel<I, O, T>>
+        where I: crate::LayerValue, O: crate::LayerValue, T: crate::LayerTrainables {
+        self.interpret_outputs(&[value])
+    }
+
+    #[cfg(feature = "llvm")]
+    pub(crate) fn compile_outputs<I, O, T>(&self, outputs: &[&crate::Value]) -> crate::Result<crate::Kernel<I, O, T>>
+        where I: crate::LayerValue, O: crate::LayerValue, T: crate::LayerTrainables {
+        let name = format!("kernel_{}", self.kernel_count.get());
+        self.kernel_count.set(self.kernel_count.get() + 1);
+
+        let optimized = crate::codegen::PassManager::default().run(outputs)?;
+        let outputs: Vec<&crate::Value> = optimized.iter().collect();
+
+        let compiler = crate::codegen::Compiler::new(unsafe { self.jit.context.get() }, &name);
+        compiler.compile(&name, &outputs)?;
+
+        self.jit.execution_engine.add_module(compiler.module())
+            .map_err(|_| crate::Error::from(Errors::CompilationFailed))?;
+
+        let function = unsafe {
+            self.jit.execution_engine.get_function::<crate::kernel::KernelFunction>(&name)
+                .map_err(|_| crate::Error::from(Errors::KernelNotFound))?
+                .as_raw()
+        };
+
+        crate::Kernel::new(crate::kernel::Backend::Jit {
+            function,
+            _jit: self.jit.clone(),
+        }, &outputs)
+    }
+
+    #[cfg(not(feature = "llvm"))]
+    pub(crate) fn compile_outputs<I, O, T>(&self, outputs: &[&crate::Value]) -> crate::Result<crate::Kernel<I, O, T>>
+        where I: crate::LayerValue, O: crate::LayerValue, T: crate::LayerTrainables {
+        let optimized = crate::codegen::PassManager::default().run(outputs)?;
+        self.interpret_outputs(&optimized.iter().collect::<Vec<_>>())
+    }
+
+    pub(crate) fn interpret_outputs<I, O, T>(&self, outputs: &[&crate::Value]) -> crate::Result<crate::Kernel<I, O, T>>
+        where I: crate::LayerValue, O: crate::LayerValue, T: crate::LayerTrainables {
+        let values = outputs.iter().map(|output| (*output).clone()).collect();
+
+        crate::Kernel::new(crate::kernel::Backend::Interpreted(values), outputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Engine;
+    use crate::{ Dimension, Graph, Kernel, Tensor };
+
+    #[test]
+    fn compiled_kernel_runs() {
+        let engine = Engine::new().unwrap();
+
+        let mut graph = Graph::new();
+        let x = graph.tensor::<f32>(Dimension::new(3, 1, 1), 2);
+        let w = graph.tensor::<f32>(Dimension::new(3, 1, 1), 2);
+        let y = x.clone().add(x).unwrap().add(w).unwrap();
+
+        let kernel: Kernel<Tensor<f32>, Tensor<f32>, Tensor<f32>> = engine.compile(&y).unwrap();
+        let input = Tensor::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], Dimension::new(3, 1, 1), 2).unwrap();
+        let trainables = Tensor::full(0.5, Dimension::new(3, 1, 1), 2).unwrap();
+
+        let output = kernel.run(&input, &trainables).unwrap();
+        assert_eq!(output.as_slice(), &[2.5, 4.5, 6.5, 8.5, 10.5, 12.5]);
+    }
+
+    #[test]
+    fn kernels_outlive_engine() {
+        let (sum, difference) = {
+            let engine = Engine::new().unwrap();
+
+            let mut graph = Graph::new();
+            let x = graph.tensor::<f64>(Dimension::new(2, 1, 1), 1);
+            let w = graph.tensor::<f64>(Dimension::new(2, 1, 1), 1);
+
+            let sum: Kernel<Tensor<f64>, Tensor<f64>, Tensor<f64>> = engine.compile(&x.clone().add(w.clone()).unwrap()).unwrap();
+            let difference: Kernel<Tensor<f64>, Tensor<f64>, Tensor<f64>> = engine.compile(&x.subtract(w).unwrap()).unwrap();
+            (sum, difference)
+        };
+
+        let input = Tensor::from_vec(vec![3.0, 5.0], Dimension::new(2, 1, 1), 1).unwrap();
+        let trainables = Tensor::from_vec(vec![1.0, 2.0], Dimension::new(2, 1, 1), 1).unwrap();
+
+        assert_eq!(sum.run(&input, &trainables).unwrap().as_slice(), &[4.0, 7.0]);
+        assert_eq!(difference.run(&input, &trainables).unwrap().as_slice(), &[2.0, 3.0]);
+    }
+}
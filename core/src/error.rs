@@ -14,6 +14,22 @@ pub(crate) enum ErrorVariants {
     RequiresTensor,
     UnableToConvergeOperand,
     UnableToConvolve,
+    UnsupportedOperand,
+    #[cfg(feature = "llvm")]
+    CompilationFailed,
+    #[cfg(feature = "llvm")]
+    EngineCreationFailed,
+    #[cfg(feature = "llvm")]
+    KernelNotFound,
+    MissingKernelParameter,
+    InvalidKernelOutput,
+    RequiresParameter,
+    DifferentChannelCounts,
+    InvalidAxis,
+    InvalidRank,
+    DifferentBatchSizes,
+    InvalidBatchSize,
+    DifferentParameterTypes,
 }
 
 impl<T> From<ErrorVariants> for Result<T> {
@@ -28,6 +44,13 @@ impl From<ErrorVariants> for Error {
     }
 }
 
+#[cfg(feature = "llvm")]
+impl From<inkwell::builder::BuilderError> for Error {
+    fn from(_: inkwell::builder::BuilderError) -> Self {
+        Error(ErrorVariants::CompilationFailed)
+    }
+}
+
 impl std::fmt::Debug for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self.0 {
@@ -43,6 +66,22 @@ impl std::fmt::Debug for Error {
             ErrorVariants::RequiresTensor => "Requires Tensor",
             ErrorVariants::UnableToConvergeOperand => "Unable to join operand",
             ErrorVariants::UnableToConvolve => "Unable to convolve operand",
+            ErrorVariants::UnsupportedOperand => "Unsupported operand",
+            #[cfg(feature = "llvm")]
+            ErrorVariants::CompilationFailed => "Compilation failed",
+            #[cfg(feature = "llvm")]
+            ErrorVariants::EngineCreationFailed => "Engine creation failed",
+            #[cfg(feature = "llvm")]
+            ErrorVariants::KernelNotFound => "Kernel not found",
+            ErrorVariants::MissingKernelParameter => "Missing kernel parameter",
+            ErrorVariants::InvalidKernelOutput => "Invalid kernel output",
+            ErrorVariants::RequiresParameter => "Requires parameter",
+            ErrorVariants::DifferentChannelCounts => "Different channel counts",
+            ErrorVariants::InvalidAxis => "Invalid axis",
+            ErrorVariants::InvalidRank => "Invalid rank",
+            ErrorVariants::DifferentBatchSizes => "Different batch sizes",
+            ErrorVariants::InvalidBatchSize => "Invalid batch size",
+            ErrorVariants::DifferentParameterTypes => "Different parameter types",
         })
     }
 }
\ No newline at end of file
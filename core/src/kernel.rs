@@ -1,7 +1,301 @@
 use std::marker::PhantomData;
+#[cfg(feature = "llvm")]
+use std::rc::Rc;
 
+use crate::codegen::GeneralType;
+use crate::Errors;
+
+/// Native signature of a compiled kernel: an array of parameter buffers
+/// followed by an array of output buffers
+#[cfg(feature = "llvm")]
+pub(crate) type KernelFunction = unsafe extern "C" fn(*const *const u8, *const *mut u8);
+
+/// What actually evaluates a kernel
+pub(crate) enum Backend {
+    #[cfg(feature = "llvm")]
+    Jit {
+        function: KernelFunction,
+        _jit: Rc<crate::engine::Jit>,
+    },
+    Interpreted(Vec<crate::Value>),
+}
+
+/// A compiled graph taking an input `I` and trainables `T` as parameters,
+/// in that order, and producing an `O`
 pub struct Kernel<I: crate::LayerValue, O: crate::LayerValue, T: crate::LayerTrainables> {
+    backend: Backend,
+    parameters: Vec<Option<GeneralType>>,
+    outputs: Vec<GeneralType>,
     _input: PhantomData<I>,
     _output: PhantomData<O>,
     _trainable: PhantomData<T>,
-}
\ No newline at end of file
+}
+
+impl<I: crate::LayerValue, O: crate::LayerValue, T: crate::LayerTrainables> Kernel<I, O, T> {
+    pub(crate) fn new(backend: Backend, outputs: &[&crate::Value]) -> crate::Result<Self> {
+        let mut parameters = Vec::new();
+
+        for output in outputs {
+            output.parameters(&mut parameters)?;
+        }
+
+        Ok(Self {
+            backend,
+            parameters,
+            outputs: outputs.iter().map(|output| output.buffer_type()).collect::<crate::Result<_>>()?,
+            _input: PhantomData,
+            _output: PhantomData,
+            _trainable: PhantomData,
+        })
+    }
+
+    pub fn run(&self, input: &I, trainables: &T) -> crate::Result<O> {
+        let mut output = O::allocate(&mut self.outputs.iter().copied())?;
+        self.run_into(input, trainables, &mut output)?;
+
+        Ok(output)
+    }
+
+    /// Runs the kernel writing into an already allocated output
+    pub(crate) fn run_into(&self, input: &I, trainables: &T, output: &mut O) -> crate::Result<()> {
+        let mut general_types = input.general_types();
+        general_types.extend(trainables.general_types());
+
+        if general_types.len() < self.parameters.len() {
+            return Errors::MissingKernelParameter.into();
+        }
+
+        for (expected, actual) in self.parameters.iter().zip(general_types.iter()) {
+            if let Some(expected) = expected {
+                check(expected, actual)?;
+            }
+        }
+
+        let output_types = output.general_types();
+
+        if output_types.len() != self.outputs.len() {
+            return Errors::InvalidKernelOutput.into();
+        }
+
+        for (expected, actual) in self.outputs.iter().zip(output_types.iter()) {
+            check(expected, actual)?;
+        }
+
+        let mut parameters = input.buffers();
+        parameters.extend(trainables.buffers());
+
+        let mut layouts = input.layouts();
+        layouts.extend(trainables.layouts());
+
+        // Planar buffers are handed to the kernel as interleaved copies
+        let mut copies = Vec::new();
+
+        for (i, general_type) in general_types.iter().enumerate() {
+            if layouts[i] == crate::ChannelLayout::Planar {
+                let copy = interleaved_copy(parameters[i], general_type);
+                parameters[i] = copy.as_ptr() as _;
+                copies.push(copy);
+            }
+        }
+
+        // Planar outputs are written interleaved first and rearranged after
+        let mut outputs = output.buffers_mut();
+        let mut planar_outputs = Vec::new();
+
+        for (i, layout) in output.layouts().into_iter().enumerate() {
+            if layout == crate::ChannelLayout::Planar {
+                let mut copy = interleaved_copy(outputs[i], &output_types[i]);
+                planar_outputs.push((i, outputs[i]));
+                outputs[i] = copy.as_mut_ptr() as _;
+                copies.push(copy);
+            }
+        }
+
+        unsafe {
+            self.call(&parameters, &outputs)?;
+        }
+
+        for (i, destination) in planar_outputs {
+            rearrange(outputs[i], destination, &output_types[i], crate::ChannelLayout::Planar);
+        }
+
+        Ok(())
+    }
+
+    /// Runs the kernel over raw buffers
+    ///
+    /// # Safety
+    /// Every buffer must be valid for the layout of the parameter or output
+    /// the kernel was compiled with at the same index
+    pub(crate) unsafe fn call(&self, parameters: &[*const u8], outputs: &[*mut u8]) -> crate::Result<()> {
+        match &self.backend {
+            #[cfg(feature = "llvm")]
+            Backend::Jit { function, .. } => {
+                function(parameters.as_ptr(), outputs.as_ptr());
+                Ok(())
+            },
+            Backend::Interpreted(values) => {
+                let values: Vec<&crate::Value> = values.iter().collect();
+                crate::codegen::Interpreter::run(&values, parameters, outputs)
+            },
+        }
+    }
+}
+
+fn check(expected: &GeneralType, actual: &GeneralType) -> crate::Result<()> {
+    match (expected, actual) {
+        (GeneralType::Tensor(a, at), GeneralType::Tensor(b, bt)) => {
+            if at != bt {
+                return Errors::DifferentOperandTypes.into();
+            }
+
+            if a != b {
+                return Errors::DifferentOperandDimensions.into();
+            }
+
+            Ok(())
+        },
+        (GeneralType::Element(at), GeneralType::Element(bt)) => {
+            if at != bt {
+                return Errors::DifferentOperandTypes.into();
+            }
+
+            Ok(())
+        },
+        _ => Errors::InvalidOperandTypes.into()
+    }
+}
+
+/// Interleaved copy of a planar buffer of `general_type`. The copy is held
+/// as `u64`s so it is aligned for any scalar type.
+fn interleaved_copy(source: *const u8, general_type: &GeneralType) -> Vec<u64> {
+    let bytes = general_type.scalars() * general_type.scalar_type().size();
+    let mut copy = vec![0u64; bytes.div_ceil(8)];
+
+    rearrange(source, copy.as_mut_ptr() as _, general_type, crate::ChannelLayout::Interleaved);
+    copy
+}
+
+/// Copies a buffer of `general_type` into `destination`, converting it
+/// into the `to` layout from the other one
+pub(crate) fn rearrange(source: *const u8, destination: *mut u8, general_type: &GeneralType, to: crate::ChannelLayout) {
+    let channels = match general_type {
+        GeneralType::Tensor(_, element) | GeneralType::Element(element) => element.0 as usize,
+    };
+
+    let size = general_type.scalar_type().size();
+    let elements = general_type.scalars() / channels.max(1);
+
+    for element in 0..elements {
+        for channel in 0..channels {
+            let interleaved = element * channels + channel;
+            let planar = channel * elements + element;
+
+            let (from, into) = match to {
+                crate::ChannelLayout::Interleaved => (planar, interleaved),
+                crate::ChannelLayout::Planar => (interleaved, planar),
+            };
+
+            unsafe {
+                destination.add(into * size).copy_from_nonoverlapping(source.add(from * size), size);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ ChannelLayout, Dimension, Engine, Graph, Kernel, Tensor };
+
+    fn message<T>(result: crate::Result<T>) -> String {
+        match result {
+            Ok(_) => String::from("Ok"),
+            Err(error) => format!("{:?}", error),
+        }
+    }
+
+    /// Kernel adding its input to its trainables, three elements of two
+    /// channels each
+    fn sum<F: crate::Scalar>() -> Kernel<Tensor<F>, Tensor<F>, Tensor<F>> {
+        let mut graph = Graph::new();
+        let x = graph.tensor::<F>(Dimension::new(3, 1, 1), 2);
+        let w = graph.tensor::<F>(Dimension::new(3, 1, 1), 2);
+
+        Engine::new().unwrap().interpret(&x.add(w).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn rejects_different_scalar_types() {
+        let mut graph = Graph::new();
+        let x = graph.tensor::<f32>(Dimension::new(3, 1, 1), 2);
+        let w = graph.tensor::<f32>(Dimension::new(3, 1, 1), 2);
+        let kernel: Kernel<Tensor<f64>, Tensor<f32>, Tensor<f32>> = Engine::new().unwrap()
+            .interpret(&x.add(w).unwrap())
+            .unwrap();
+
+        let input = Tensor::zeros(Dimension::new(3, 1, 1), 2).unwrap();
+        let trainables = Tensor::zeros(Dimension::new(3, 1, 1), 2).unwrap();
+        assert_eq!(message(kernel.run(&input, &trainables)), "Different operand types");
+    }
+
+    #[test]
+    fn rejects_different_dimensions() {
+        let kernel = sum::<f32>();
+        let input = Tensor::zeros(Dimension::new(2, 1, 1), 2).unwrap();
+        let trainables = Tensor::zeros(Dimension::new(3, 1, 1), 2).unwrap();
+
+        assert_eq!(message(kernel.run(&input, &trainables)), "Different operand dimensions");
+    }
+
+    #[test]
+    fn rejects_missing_parameters() {
+        let mut graph = Graph::new();
+        let x = graph.tensor::<f32>(Dimension::new(3, 1, 1), 1);
+        let w = graph.tensor::<f32>(Dimension::new(3, 1, 1), 1);
+        let b = graph.tensor::<f32>(Dimension::new(3, 1, 1), 1);
+        let kernel: Kernel<Tensor<f32>, Tensor<f32>, Tensor<f32>> = Engine::new().unwrap()
+            .interpret(&x.add(w).unwrap().add(b).unwrap())
+            .unwrap();
+
+        let operand = Tensor::zeros(Dimension::new(3, 1, 1), 1).unwrap();
+        assert_eq!(message(kernel.run(&operand, &operand)), "Missing kernel parameter");
+    }
+
+    #[test]
+    fn rejects_different_outputs() {
+        let kernel = sum::<f32>();
+        let operand = Tensor::zeros(Dimension::new(3, 1, 1), 2).unwrap();
+        let mut output = Tensor::zeros(Dimension::new(3, 1, 1), 1).unwrap();
+
+        assert_eq!(message(kernel.run_into(&operand, &operand, &mut output)), "Different operand types");
+    }
+
+    #[test]
+    fn copies_planar_inputs() {
+        let kernel = sum::<f64>();
+        let input = Tensor::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0], Dimension::new(3, 1, 1), 2).unwrap();
+        let trainables = Tensor::from_vec(vec![10.0, 20.0, 30.0, 40.0, 50.0, 60.0], Dimension::new(3, 1, 1), 2).unwrap();
+        let expected = [11.0, 22.0, 33.0, 44.0, 55.0, 66.0];
+
+        let output = kernel.run(&input.clone().into_layout(ChannelLayout::Planar), &trainables).unwrap();
+        assert_eq!(output.layout(), ChannelLayout::Interleaved);
+        assert_eq!(output.as_slice(), &expected);
+
+        // The planar input is left untouched
+        let planar = input.into_layout(ChannelLayout::Planar);
+        kernel.run(&planar, &trainables.into_layout(ChannelLayout::Planar)).unwrap();
+        assert_eq!(planar.as_slice(), &[1.0, 3.0, 5.0, 2.0, 4.0, 6.0]);
+    }
+
+    #[test]
+    fn rearranges_planar_outputs() {
+        let kernel = sum::<i32>();
+        let input = Tensor::from_vec(vec![1, 2, 3, 4, 5, 6], Dimension::new(3, 1, 1), 2).unwrap();
+        let trainables = Tensor::full(1, Dimension::new(3, 1, 1), 2).unwrap();
+        let mut output = Tensor::zeros(Dimension::new(3, 1, 1), 2).unwrap().into_layout(ChannelLayout::Planar);
+
+        kernel.run_into(&input, &trainables, &mut output).unwrap();
+        assert_eq!(output.layout(), ChannelLayout::Planar);
+        assert_eq!(output.as_slice(), &[2, 4, 6, 3, 5, 7]);
+    }
+}
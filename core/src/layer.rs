@@ -1,5 +1,31 @@
 pub trait Layer where Self::Trainables: crate::LayerTrainables {
     type Trainables;
 
-    fn operations(&self, input: crate::Value, trainables: <Self::Trainables as crate::LayerTrainables>::Values) -> crate::Value;
-}
\ No newline at end of file
+    /// Graph of this layer applied to `input`, a placeholder or any value
+    /// built from one, reading its trainables from `trainables`
+    fn operations(&self, input: crate::Value, trainables: <Self::Trainables as crate::LayerTrainables>::Values) -> crate::Result<crate::Value>;
+
+    /// Builds the graph of this layer over parameters shaped like `input`
+    /// and `trainables`, in the order a kernel takes them
+    fn value<I: crate::LayerValue>(&self, input: &I, trainables: &Self::Trainables) -> crate::Result<crate::Value> {
+        self.batched_value(input, 1, trainables)
+    }
+
+    /// Builds the graph of this layer over `batch` samples shaped like
+    /// `input` at once. The kernel takes them as a single tensor with one
+    /// more axis counting the samples, as `Tensor::stack` builds along the
+    /// axis past the last one.
+    fn batched_value<I: crate::LayerValue>(&self, input: &I, batch: u32, trainables: &Self::Trainables) -> crate::Result<crate::Value> {
+        let general_types = input.general_types();
+
+        if general_types.len() != 1 {
+            return crate::Errors::InvalidOperandTypes.into();
+        }
+
+        let mut graph = crate::Graph::new();
+        let input = graph.batched_input(general_types[0], batch)?;
+        let trainables = graph.trainables(trainables);
+
+        self.operations(input, trainables)
+    }
+}
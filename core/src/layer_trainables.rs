@@ -1,11 +1,23 @@
-pub trait LayerTrainables {
+pub trait LayerTrainables: crate::LayerValue {
     type Values;
+
+    /// Kernel parameters reading these trainables, numbered from `first`
+    fn values(&self, first: u32) -> Self::Values;
 }
 
-impl<F> LayerTrainables for crate::Tensor<F> {
+impl<F: crate::Scalar> LayerTrainables for crate::Tensor<F> {
     type Values = crate::Value;
+
+    fn values(&self, first: u32) -> Self::Values {
+        crate::Value::input(first, crate::LayerValue::general_types(self)[0])
+    }
 }
 
-impl<F> LayerTrainables for (crate::Tensor<F>, crate::Tensor<F>) {
-    type Values = (crate::Value, crate::Value);
-}
\ No newline at end of file
+impl<A: LayerTrainables, B: LayerTrainables> LayerTrainables for (A, B) {
+    type Values = (A::Values, B::Values);
+
+    fn values(&self, first: u32) -> Self::Values {
+        let count = self.0.general_types().len() as u32;
+        (self.0.values(first), self.1.values(first + count))
+    }
+}
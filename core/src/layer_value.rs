@@ -0,0 +1,107 @@
+use crate::codegen::{ GeneralType, ElementType };
+use crate::Errors;
+
+/// Data a kernel reads as an input or writes as an output, passed to the
+/// kernel as one buffer per `GeneralType`
+pub trait LayerValue: Sized {
+    fn general_types(&self) -> Vec<GeneralType>;
+
+    /// Buffers the kernel reads
+    fn buffers(&self) -> Vec<*const u8>;
+
+    /// Buffers the kernel writes into
+    fn buffers_mut(&mut self) -> Vec<*mut u8>;
+
+    /// Channel layout of every buffer. Kernels work on interleaved buffers,
+    /// so planar ones are rearranged around each run.
+    fn layouts(&self) -> Vec<crate::ChannelLayout> {
+        vec![crate::ChannelLayout::Interleaved; self.general_types().len()]
+    }
+
+    /// Allocates a zeroed value, taking its types from the front of
+    /// `general_types`
+    fn allocate(general_types: &mut dyn Iterator<Item = GeneralType>) -> crate::Result<Self>;
+}
+
+impl<F: crate::Scalar> LayerValue for crate::Tensor<F> {
+    fn general_types(&self) -> Vec<GeneralType> {
+        vec![GeneralType::Tensor(*self.dimension(), ElementType(self.channels() as u32, F::scalar_type()))]
+    }
+
+    fn buffers(&self) -> Vec<*const u8> {
+        vec![self.as_ptr() as _]
+    }
+
+    fn buffers_mut(&mut self) -> Vec<*mut u8> {
+        vec![self.as_mut_ptr() as _]
+    }
+
+    fn layouts(&self) -> Vec<crate::ChannelLayout> {
+        vec![self.layout()]
+    }
+
+    fn allocate(general_types: &mut dyn Iterator<Item = GeneralType>) -> crate::Result<Self> {
+        match general_types.next() {
+            Some(GeneralType::Tensor(dimension, ElementType(channels, scalar))) if scalar == F::scalar_type() => {
+                Self::zeroed(dimension, channels as usize)
+            },
+            _ => Errors::InvalidKernelOutput.into()
+        }
+    }
+}
+
+impl<F: crate::Scalar> LayerValue for crate::Element<F> {
+    fn general_types(&self) -> Vec<GeneralType> {
+        vec![GeneralType::Element(ElementType(self.channels() as u32, F::scalar_type()))]
+    }
+
+    fn buffers(&self) -> Vec<*const u8> {
+        vec![self.as_ptr() as _]
+    }
+
+    fn buffers_mut(&mut self) -> Vec<*mut u8> {
+        vec![self.as_mut_ptr() as _]
+    }
+
+    fn allocate(general_types: &mut dyn Iterator<Item = GeneralType>) -> crate::Result<Self> {
+        match general_types.next() {
+            Some(GeneralType::Element(ElementType(channels, scalar))) if scalar == F::scalar_type() => {
+                Self::zeroed(channels as usize)
+            },
+            _ => Errors::InvalidKernelOutput.into()
+        }
+    }
+}
+
+impl<A: LayerValue, B: LayerValue> LayerValue for (A, B) {
+    fn general_types(&self) -> Vec<GeneralType> {
+        let mut general_types = self.0.general_types();
+        general_types.extend(self.1.general_types());
+        general_types
+    }
+
+    fn buffers(&self) -> Vec<*const u8> {
+        let mut buffers = self.0.buffers();
+        buffers.extend(self.1.buffers());
+        buffers
+    }
+
+    fn buffers_mut(&mut self) -> Vec<*mut u8> {
+        let mut buffers = self.0.buffers_mut();
+        buffers.extend(self.1.buffers_mut());
+        buffers
+    }
+
+    fn layouts(&self) -> Vec<crate::ChannelLayout> {
+        let mut layouts = self.0.layouts();
+        layouts.extend(self.1.layouts());
+        layouts
+    }
+
+    fn allocate(general_types: &mut dyn Iterator<Item = GeneralType>) -> crate::Result<Self> {
+        let a = A::allocate(general_types)?;
+        let b = B::allocate(general_types)?;
+
+        Ok((a, b))
+    }
+}
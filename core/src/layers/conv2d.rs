@@ -0,0 +1,93 @@
+use std::marker::PhantomData;
+
+use crate::random::Random;
+
+/// 2D convolution over a `width x height x depth` tensor producing one
+/// plane per filter. The filters are held as a single weight tensor of
+/// `size.0 * size.1 * depth x filters`, each column a filter flattened in
+/// the order of the window it is applied to.
+pub struct Conv2d<F: crate::Scalar> {
+    depth: u32,
+    filters: u32,
+    size: (u32, u32),
+    stride: (u32, u32),
+    padding: (u32, u32),
+    activation_fn: Option<crate::ActivationFunction>,
+    _scalar: PhantomData<F>,
+}
+
+impl<F: crate::Scalar> Conv2d<F> {
+    pub fn new(depth: u32, filters: u32, size: (u32, u32), stride: (u32, u32), padding: (u32, u32),
+        activation_fn: Option<crate::ActivationFunction>) -> Self {
+        Self {
+            depth,
+            filters,
+            size,
+            stride,
+            padding,
+            activation_fn,
+            _scalar: PhantomData,
+        }
+    }
+
+    pub fn filters(&self) -> u32 {
+        self.filters
+    }
+
+    /// Dimension of the output for an input of `dimension`
+    pub fn output_dimension(&self, dimension: crate::Dimension) -> crate::Result<crate::Dimension> {
+        let x = dimension.x() + 2 * self.padding.0;
+        let y = dimension.y() + 2 * self.padding.1;
+
+        if dimension.rank() != 3 || dimension.z() != self.depth || self.size.0 > x || self.size.1 > y
+            || self.stride.0 == 0 || self.stride.1 == 0 {
+            return crate::Errors::UnableToConvolve.into();
+        }
+
+        Ok(crate::Dimension::new(
+            (x - self.size.0) / self.stride.0 + 1,
+            (y - self.size.1) / self.stride.1 + 1,
+            self.filters))
+    }
+
+    /// Filters filled by `initializer` and zeroed biases
+    pub fn trainables(&self, initializer: super::Initializer, seed: u64) -> crate::Result<(crate::Tensor<F>, crate::Tensor<F>)> {
+        let mut random = Random::new(seed);
+        let area = self.size.0 * self.size.1;
+
+        let filters = initializer.tensor(crate::Dimension::new(area * self.depth, self.filters, 1),
+            area * self.depth, area * self.filters, &mut random)?;
+        let biases = crate::Tensor::zeroed(crate::Dimension::new(1, self.filters, 1), 1)?;
+
+        Ok((filters, biases))
+    }
+}
+
+impl<F: crate::Scalar> crate::Layer for Conv2d<F> {
+    type Trainables = (crate::Tensor<F>, crate::Tensor<F>);
+
+    fn operations(&self, input: crate::Value, trainables: (crate::Value, crate::Value)) -> crate::Result<crate::Value> {
+        let (filters, biases) = trainables;
+
+        let input = if self.padding != (0, 0) {
+            input.pad(self.padding)?
+        } else {
+            input
+        };
+
+        // Every window becomes a row multiplied against all filters at once,
+        // then a column of one scalar per filter summed into its position
+        let window = input.convolve(self.size, self.stride)?
+            .reshape(crate::Dimension::new(1, self.size.0 * self.size.1 * self.depth, 1))?;
+
+        let output = window.multiply(filters)?
+            .add(biases)?
+            .reshape(crate::Dimension::new(1, 1, self.filters))?
+            .converge_sum()?;
+
+        match &self.activation_fn {
+            Some(activation_fn) => output.activation(activation_fn),
+            None => Ok(output),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::marker::PhantomData;
+
+use crate::random::Random;
+
+/// Fully connected layer mapping a `1 x inputs` row to a `1 x outputs` row
+/// through a weight tensor of `inputs x outputs` and a bias row
+pub struct Dense<F: crate::Scalar> {
+    inputs: u32,
+    outputs: u32,
+    activation_fn: Option<crate::ActivationFunction>,
+    _scalar: PhantomData<F>,
+}
+
+impl<F: crate::Scalar> Dense<F> {
+    pub fn new(inputs: u32, outputs: u32, activation_fn: Option<crate::ActivationFunction>) -> Self {
+        Self {
+            inputs,
+            outputs,
+            activation_fn,
+            _scalar: PhantomData,
+        }
+    }
+
+    pub fn inputs(&self) -> u32 {
+        self.inputs
+    }
+
+    pub fn outputs(&self) -> u32 {
+        self.outputs
+    }
+
+    /// Weights filled by `initializer` and zeroed biases
+    pub fn trainables(&self, initializer: super::Initializer, seed: u64) -> crate::Result<(crate::Tensor<F>, crate::Tensor<F>)> {
+        let mut random = Random::new(seed);
+
+        let weights = initializer.tensor(crate::Dimension::new(self.inputs, self.outputs, 1),
+            self.inputs, self.outputs, &mut random)?;
+        let biases = crate::Tensor::zeroed(crate::Dimension::new(1, self.outputs, 1), 1)?;
+
+        Ok((weights, biases))
+    }
+}
+
+impl<F: crate::Scalar> crate::Layer for Dense<F> {
+    type Trainables = (crate::Tensor<F>, crate::Tensor<F>);
+
+    fn operations(&self, input: crate::Value, trainables: (crate::Value, crate::Value)) -> crate::Result<crate::Value> {
+        let (weights, biases) = trainables;
+        let output = input.multiply(weights)?.add(biases)?;
+
+        match &self.activation_fn {
+            Some(activation_fn) => output.activation(activation_fn),
+            None => Ok(output),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::Scalar;
+use crate::random::Random;
+
+/// How the weights of a layer are filled before training
+#[derive(Clone, Copy)]
+#[derive(PartialEq)]
+pub enum Initializer {
+    Zeros,
+    Constant(f64),
+    Uniform(f64, f64),
+    Normal(f64, f64),
+    /// Uniform over `±sqrt(6 / (fan_in + fan_out))`
+    Xavier,
+    /// Normal with a standard deviation of `sqrt(2 / fan_in)`
+    He,
+}
+
+impl Initializer {
+    pub(crate) fn tensor<F: Scalar>(&self, dimension: crate::Dimension, fan_in: u32, fan_out: u32, random: &mut Random) -> crate::Result<crate::Tensor<F>> {
+        let mut tensor = crate::Tensor::<F>::zeroed(dimension, 1)?;
+
+        for scalar in tensor.as_mut_slice() {
+            let value = match *self {
+                Initializer::Zeros => 0.0,
+                Initializer::Constant(value) => value,
+                Initializer::Uniform(low, high) => random.uniform(low, high),
+                Initializer::Normal(mean, deviation) => random.normal(mean, deviation),
+                Initializer::Xavier => {
+                    let limit = (6.0 / (fan_in + fan_out) as f64).sqrt();
+                    random.uniform(-limit, limit)
+                },
+                Initializer::He => random.normal(0.0, (2.0 / fan_in as f64).sqrt()),
+            };
+
+            *scalar = F::from_f64(value);
+        }
+
+        Ok(tensor)
+    }
+}
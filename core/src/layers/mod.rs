@@ -0,0 +1,9 @@
+mod dense;
+mod conv2d;
+mod sequential;
+mod initializer;
+
+pub use dense::Dense;
+pub use conv2d::Conv2d;
+pub use sequential::Sequential;
+pub use initializer::Initializer;
@@ -0,0 +1,63 @@
+use crate::{ Layer, LayerTrainables };
+
+/// Chains two layers, feeding the output of the first into the second.
+/// Longer chains nest, with their trainables nesting the same way.
+pub struct Sequential<A: Layer, B: Layer> {
+    first: A,
+    second: B,
+}
+
+impl<A: Layer, B: Layer> Sequential<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+        }
+    }
+
+    /// Appends `layer` after the layers already chained
+    pub fn then<C: Layer>(self, layer: C) -> Sequential<Self, C> {
+        Sequential::new(self, layer)
+    }
+
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Compiles the whole chain into a single kernel taking an input shaped
+    /// like `input` and the trainables of every layer
+    pub fn compile<I, O>(&self, engine: &crate::Engine, input: &I, trainables: &<Self as Layer>::Trainables)
+        -> crate::Result<crate::Kernel<I, O, <Self as Layer>::Trainables>>
+        where I: crate::LayerValue, O: crate::LayerValue {
+        engine.compile(&self.value(input, trainables)?)
+    }
+
+    /// Compiles the whole chain into a single kernel taking `batch` samples
+    /// shaped like `input` at once, stacked into an `S`
+    pub fn compile_batched<I, S, O>(&self, engine: &crate::Engine, input: &I, batch: u32, trainables: &<Self as Layer>::Trainables)
+        -> crate::Result<crate::Kernel<S, O, <Self as Layer>::Trainables>>
+        where I: crate::LayerValue, S: crate::LayerValue, O: crate::LayerValue {
+        engine.compile(&self.batched_value(input, batch, trainables)?)
+    }
+}
+
+impl<A: Layer, B: Layer> Layer for Sequential<A, B> {
+    type Trainables = (A::Trainables, B::Trainables);
+
+    fn operations(&self, input: crate::Value, trainables: <Self::Trainables as LayerTrainables>::Values) -> crate::Result<crate::Value> {
+        let (first, second) = trainables;
+        let output = self.first.operations(input, first)?;
+
+        // A layer may only hand on a complete value, not one still spread
+        // over convolution windows
+        if output.is_convergent() {
+            return crate::Errors::UnableToConvergeOperand.into();
+        }
+
+        self.second.operations(output, second)
+    }
+}
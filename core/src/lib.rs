@@ -2,23 +2,39 @@ mod engine;
 mod codegen;
 mod layer;
 mod layer_trainables;
+mod layer_value;
+mod optimizer;
 mod kernel;
 mod tensor;
+mod tensor_arithmetic;
+mod tensor_shape;
 mod element;
 mod dimension;
+mod channel_layout;
+mod scalar;
 mod error;
 mod activation_function;
+mod loss;
+mod random;
+mod trainer;
 
 use error::ErrorVariants as Errors;
 pub use error::Error;
 pub use error::Result;
 pub mod layers;
+pub mod optimizers;
 pub use engine::Engine;
-pub use codegen::Value;
+pub use codegen::{ Value, Graph, GeneralType, ElementType, ScalarType };
 pub use layer::Layer;
 pub use layer_trainables::LayerTrainables;
+pub use layer_value::LayerValue;
+pub use optimizer::Optimizer;
 pub use kernel::Kernel;
-pub use dimension::Dimension;
+pub use dimension::{ Dimension, MAX_RANK };
+pub use scalar::Scalar;
 pub use tensor::Tensor;
+pub use channel_layout::ChannelLayout;
 pub use activation_function::ActivationFunction;
+pub use loss::Loss;
+pub use trainer::{ Trainer, Metrics, Control };
 pub use element::{ Element, ChannelCount };
\ No newline at end of file
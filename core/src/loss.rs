@@ -0,0 +1,11 @@
+#[derive(Clone, Copy)]
+#[derive(PartialEq)]
+pub enum Loss {
+    MeanSquaredError,
+    MeanAbsoluteError,
+    BinaryCrossEntropy,
+    /// Cross-entropy of the softmax of the predictions over all of their
+    /// scalars, the predictions being logits
+    CategoricalCrossEntropy,
+    Huber(f32),
+}
@@ -0,0 +1,118 @@
+use crate::Errors;
+
+/// Applies gradients to trainables, such as those produced by
+/// `Value::gradient` for every tensor of a `LayerTrainables`. Updates run on
+/// the host between kernel calls, over `f64` copies of the scalars.
+pub trait Optimizer {
+    /// Moves every trainable against its gradient, `gradients` holding one
+    /// gradient of the same type for each of them
+    fn step<T: crate::LayerTrainables>(&mut self, trainables: &mut T, gradients: &T) -> crate::Result<()>;
+}
+
+/// Host copies of the scalars of a trainable and of its gradient, kept by
+/// optimizers so every step reuses them
+#[derive(Default)]
+pub(crate) struct Scratch {
+    values: Vec<f64>,
+    gradient: Vec<f64>,
+}
+
+/// Runs `update` over the scalars of every trainable and its gradient,
+/// numbering them in the order of their buffers, and writes the updated
+/// scalars back
+pub(crate) fn update<T, U>(trainables: &mut T, gradients: &T, scratch: &mut Scratch, mut update: U) -> crate::Result<()>
+    where T: crate::LayerTrainables, U: FnMut(usize, &mut [f64], &[f64]) {
+
+    let general_types = trainables.general_types();
+
+    if general_types != gradients.general_types() {
+        return Errors::DifferentOperandTypes.into();
+    }
+
+    for general_type in &general_types {
+        if !general_type.scalar_type().is_float() {
+            return Errors::InvalidOperandTypes.into();
+        }
+    }
+
+    let parameters = trainables.buffers_mut();
+    let gradients = gradients.buffers();
+
+    for (slot, general_type) in general_types.iter().enumerate() {
+        let scalar = general_type.scalar_type();
+        let count = general_type.scalars();
+
+        unsafe {
+            scratch.values.clear();
+            scratch.values.extend((0..count).map(|i| scalar.read(parameters[slot], i)));
+            scratch.gradient.clear();
+            scratch.gradient.extend((0..count).map(|i| scalar.read(gradients[slot], i)));
+
+            update(slot, &mut scratch.values, &scratch.gradient);
+
+            for (i, value) in scratch.values.iter().enumerate() {
+                scalar.write(parameters[slot], i, *value);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Per-trainable optimizer state, created zeroed on first use
+pub(crate) fn state(states: &mut Vec<Vec<f64>>, slot: usize, count: usize) -> &mut Vec<f64> {
+    if states.len() <= slot {
+        states.resize(slot + 1, Vec::new());
+    }
+
+    if states[slot].len() != count {
+        states[slot] = vec![0.0; count];
+    }
+
+    &mut states[slot]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ Dimension, Optimizer, Tensor };
+    use crate::optimizers::Sgd;
+
+    fn message<T>(result: crate::Result<T>) -> String {
+        match result {
+            Ok(_) => String::from("Ok"),
+            Err(error) => format!("{:?}", error),
+        }
+    }
+
+    #[test]
+    fn rejects_different_gradients() {
+        let mut trainables = Tensor::<f64>::zeros(Dimension::new(2, 1, 1), 1).unwrap();
+
+        let larger = Tensor::<f64>::zeros(Dimension::new(3, 1, 1), 1).unwrap();
+        assert_eq!(message(Sgd::new(0.1).step(&mut trainables, &larger)), "Different operand types");
+
+        let channels = Tensor::<f64>::zeros(Dimension::new(2, 1, 1), 2).unwrap();
+        assert_eq!(message(Sgd::new(0.1).step(&mut trainables, &channels)), "Different operand types");
+    }
+
+    #[test]
+    fn rejects_integer_trainables() {
+        let mut trainables = Tensor::<i32>::zeros(Dimension::new(2, 1, 1), 1).unwrap();
+        let gradients = Tensor::<i32>::zeros(Dimension::new(2, 1, 1), 1).unwrap();
+
+        assert_eq!(message(Sgd::new(0.1).step(&mut trainables, &gradients)), "Invalid operand types");
+    }
+
+    #[test]
+    fn updates_every_trainable() {
+        let mut trainables = (Tensor::full(1.0f64, Dimension::new(2, 1, 1), 1).unwrap(),
+            Tensor::full(2.0f32, Dimension::new(3, 1, 1), 1).unwrap());
+        let gradients = (Tensor::full(0.5f64, Dimension::new(2, 1, 1), 1).unwrap(),
+            Tensor::full(-1.0f32, Dimension::new(3, 1, 1), 1).unwrap());
+
+        Sgd::new(0.5).step(&mut trainables, &gradients).unwrap();
+
+        assert_eq!(trainables.0.as_slice(), &[0.75, 0.75]);
+        assert_eq!(trainables.1.as_slice(), &[2.5, 2.5, 2.5]);
+    }
+}
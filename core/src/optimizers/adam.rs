@@ -0,0 +1,145 @@
+use crate::optimizer::{ update, state, Scratch };
+
+/// Adam, steps scaled by bias corrected running averages of the gradients
+/// and of their squares
+pub struct Adam {
+    learning_rate: f64,
+    beta1: f64,
+    beta2: f64,
+    epsilon: f64,
+    /// Weight decay and whether it is decoupled from the gradient
+    weight_decay: (f64, bool),
+    steps: i32,
+    means: Vec<Vec<f64>>,
+    variances: Vec<Vec<f64>>,
+    scratch: Scratch,
+}
+
+/// Adam with weight decay applied directly to the trainables rather than
+/// through their gradients
+pub struct AdamW(Adam);
+
+impl Adam {
+    pub fn new(learning_rate: f64, beta1: f64, beta2: f64, epsilon: f64) -> Self {
+        Self {
+            learning_rate,
+            beta1,
+            beta2,
+            epsilon,
+            weight_decay: (0.0, false),
+            steps: 0,
+            means: Vec::new(),
+            variances: Vec::new(),
+            scratch: Scratch::default(),
+        }
+    }
+
+    /// Adam adding an L2 penalty of `weight_decay` to every gradient
+    pub fn with_weight_decay(learning_rate: f64, beta1: f64, beta2: f64, epsilon: f64, weight_decay: f64) -> Self {
+        Self {
+            weight_decay: (weight_decay, false),
+            ..Self::new(learning_rate, beta1, beta2, epsilon)
+        }
+    }
+}
+
+impl AdamW {
+    pub fn new(learning_rate: f64, beta1: f64, beta2: f64, epsilon: f64, weight_decay: f64) -> Self {
+        Self(Adam {
+            weight_decay: (weight_decay, true),
+            ..Adam::new(learning_rate, beta1, beta2, epsilon)
+        })
+    }
+}
+
+impl crate::Optimizer for Adam {
+    fn step<T: crate::LayerTrainables>(&mut self, trainables: &mut T, gradients: &T) -> crate::Result<()> {
+        self.steps += 1;
+
+        let correction1 = 1.0 - self.beta1.powi(self.steps);
+        let correction2 = 1.0 - self.beta2.powi(self.steps);
+        let (weight_decay, decoupled) = self.weight_decay;
+
+        update(trainables, gradients, &mut self.scratch, |slot, values, gradient| {
+            let means = state(&mut self.means, slot, values.len());
+            let variances = state(&mut self.variances, slot, values.len());
+
+            for i in 0..values.len() {
+                let gradient = if decoupled {
+                    values[i] -= self.learning_rate * weight_decay * values[i];
+                    gradient[i]
+                } else {
+                    gradient[i] + weight_decay * values[i]
+                };
+
+                means[i] = self.beta1 * means[i] + (1.0 - self.beta1) * gradient;
+                variances[i] = self.beta2 * variances[i] + (1.0 - self.beta2) * gradient * gradient;
+
+                let mean = means[i] / correction1;
+                let variance = variances[i] / correction2;
+
+                values[i] -= self.learning_rate * mean / (variance.sqrt() + self.epsilon);
+            }
+        })
+    }
+}
+
+impl crate::Optimizer for AdamW {
+    fn step<T: crate::LayerTrainables>(&mut self, trainables: &mut T, gradients: &T) -> crate::Result<()> {
+        self.0.step(trainables, gradients)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ Adam, AdamW };
+    use crate::{ Dimension, Optimizer, Tensor };
+
+    fn tensor(data: &[f64]) -> Tensor<f64> {
+        Tensor::from_slice(data, Dimension::new(data.len() as u32, 1, 1), 1).unwrap()
+    }
+
+    fn assert_close(actual: &[f64], expected: &[f64], tolerance: f64) {
+        for (a, b) in actual.iter().zip(expected) {
+            assert!((a - b).abs() < tolerance, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn first_step_follows_the_sign() {
+        let mut optimizer = Adam::new(0.01, 0.9, 0.999, 0.0);
+        let mut trainables = tensor(&[1.0, 1.0, 1.0]);
+
+        optimizer.step(&mut trainables, &tensor(&[3.0, -0.5, 1e-3])).unwrap();
+        assert_close(trainables.as_slice(), &[0.99, 1.01, 0.99], 1e-12);
+    }
+
+    #[test]
+    fn second_step_uses_corrected_averages() {
+        let mut optimizer = Adam::new(0.01, 0.9, 0.999, 0.0);
+        let mut trainables = tensor(&[1.0]);
+
+        optimizer.step(&mut trainables, &tensor(&[1.0])).unwrap();
+        optimizer.step(&mut trainables, &tensor(&[-1.0])).unwrap();
+
+        // Mean (0.09 - 0.1) / 0.19 and variance 0.001999 / 0.001999
+        assert_close(trainables.as_slice(), &[0.99 + 0.01 * 0.01 / 0.19], 1e-12);
+    }
+
+    #[test]
+    fn decoupled_weight_decay() {
+        let gradients = tensor(&[0.0]);
+
+        // The penalty is a gradient of 0.5, taking a full first step
+        let mut coupled = Adam::with_weight_decay(0.1, 0.9, 0.999, 1e-8, 0.5);
+        let mut trainables = tensor(&[1.0]);
+        coupled.step(&mut trainables, &gradients).unwrap();
+        assert_close(trainables.as_slice(), &[0.9], 1e-6);
+
+        // Decay shrinks the trainable by lr * decay and the gradient stays 0
+        let mut decoupled = AdamW::new(0.1, 0.9, 0.999, 1e-8, 0.5);
+        let mut trainables = tensor(&[1.0]);
+        decoupled.step(&mut trainables, &gradients).unwrap();
+        assert_close(trainables.as_slice(), &[0.95], 1e-12);
+    }
+}
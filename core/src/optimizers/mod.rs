@@ -0,0 +1,7 @@
+mod sgd;
+mod rms_prop;
+mod adam;
+
+pub use sgd::Sgd;
+pub use rms_prop::RmsProp;
+pub use adam::{ Adam, AdamW };
@@ -0,0 +1,64 @@
+use crate::optimizer::{ update, state, Scratch };
+
+/// Scales every step by a running average of the squared gradients
+pub struct RmsProp {
+    learning_rate: f64,
+    decay: f64,
+    epsilon: f64,
+    squares: Vec<Vec<f64>>,
+    scratch: Scratch,
+}
+
+impl RmsProp {
+    pub fn new(learning_rate: f64, decay: f64, epsilon: f64) -> Self {
+        Self {
+            learning_rate,
+            decay,
+            epsilon,
+            squares: Vec::new(),
+            scratch: Scratch::default(),
+        }
+    }
+}
+
+impl crate::Optimizer for RmsProp {
+    fn step<T: crate::LayerTrainables>(&mut self, trainables: &mut T, gradients: &T) -> crate::Result<()> {
+        update(trainables, gradients, &mut self.scratch, |slot, values, gradient| {
+            let squares = state(&mut self.squares, slot, values.len());
+
+            for i in 0..values.len() {
+                squares[i] = self.decay * squares[i] + (1.0 - self.decay) * gradient[i] * gradient[i];
+                values[i] -= self.learning_rate * gradient[i] / (squares[i].sqrt() + self.epsilon);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RmsProp;
+    use crate::{ Dimension, Optimizer, Tensor };
+
+    #[test]
+    fn scales_steps_by_squared_gradients() {
+        let mut optimizer = RmsProp::new(0.1, 0.9, 0.0);
+        let mut trainables = Tensor::full(1.0f64, Dimension::new(2, 1, 1), 1).unwrap();
+        let gradients = Tensor::from_vec(vec![2.0, -0.5], Dimension::new(2, 1, 1), 1).unwrap();
+
+        // Averages of 0.1g², so every step is 0.1 / sqrt(0.1) whatever g
+        optimizer.step(&mut trainables, &gradients).unwrap();
+        let step = 0.1 / 0.1f64.sqrt();
+
+        for (value, expected) in trainables.as_slice().iter().zip([1.0 - step, 1.0 + step]) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+
+        // Then of 0.19g²
+        optimizer.step(&mut trainables, &gradients).unwrap();
+        let second = 0.1 / 0.19f64.sqrt();
+
+        for (value, expected) in trainables.as_slice().iter().zip([1.0 - step - second, 1.0 + step + second]) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+    }
+}
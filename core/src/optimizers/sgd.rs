@@ -0,0 +1,104 @@
+use crate::optimizer::{ update, state, Scratch };
+
+/// Stochastic gradient descent, optionally with (Nesterov) momentum
+pub struct Sgd {
+    learning_rate: f64,
+    momentum: f64,
+    nesterov: bool,
+    velocities: Vec<Vec<f64>>,
+    scratch: Scratch,
+}
+
+impl Sgd {
+    pub fn new(learning_rate: f64) -> Self {
+        Self::with_momentum(learning_rate, 0.0, false)
+    }
+
+    pub fn with_momentum(learning_rate: f64, momentum: f64, nesterov: bool) -> Self {
+        Self {
+            learning_rate,
+            momentum,
+            nesterov,
+            velocities: Vec::new(),
+            scratch: Scratch::default(),
+        }
+    }
+}
+
+impl crate::Optimizer for Sgd {
+    fn step<T: crate::LayerTrainables>(&mut self, trainables: &mut T, gradients: &T) -> crate::Result<()> {
+        update(trainables, gradients, &mut self.scratch, |slot, values, gradient| {
+            if self.momentum == 0.0 {
+                for (value, gradient) in values.iter_mut().zip(gradient) {
+                    *value -= self.learning_rate * gradient;
+                }
+
+                return;
+            }
+
+            let velocity = state(&mut self.velocities, slot, values.len());
+
+            for i in 0..values.len() {
+                velocity[i] = self.momentum * velocity[i] + gradient[i];
+
+                let step = if self.nesterov {
+                    gradient[i] + self.momentum * velocity[i]
+                } else {
+                    velocity[i]
+                };
+
+                values[i] -= self.learning_rate * step;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sgd;
+    use crate::{ Dimension, Optimizer, Tensor };
+
+    fn tensor(data: &[f64]) -> Tensor<f64> {
+        Tensor::from_slice(data, Dimension::new(data.len() as u32, 1, 1), 1).unwrap()
+    }
+
+    /// Trainables after a step with each of `gradients` in turn
+    fn steps(mut optimizer: Sgd, trainables: &[f64], gradients: &[&[f64]]) -> Vec<f64> {
+        let mut trainables = tensor(trainables);
+
+        for gradient in gradients {
+            optimizer.step(&mut trainables, &tensor(gradient)).unwrap();
+        }
+
+        trainables.as_slice().to_vec()
+    }
+
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        for (a, b) in actual.iter().zip(expected) {
+            assert!((a - b).abs() < 1e-12, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn plain_steps() {
+        assert_close(&steps(Sgd::new(0.1), &[1.0, -2.0], &[&[0.5, 0.25]]), &[0.95, -2.025]);
+        assert_close(&steps(Sgd::new(0.1), &[1.0, -2.0], &[&[0.5, 0.25], &[-1.0, 0.25]]), &[1.05, -2.05]);
+    }
+
+    #[test]
+    fn momentum_steps() {
+        // Velocities of 1 then 0.9 * 1 + 1
+        let optimizer = Sgd::with_momentum(0.1, 0.9, false);
+        assert_close(&steps(optimizer, &[0.0], &[&[1.0], &[1.0]]), &[-0.1 - 0.19]);
+    }
+
+    #[test]
+    fn nesterov_steps() {
+        // Steps of g + μv: 1 + 0.9 * 1, then 1 + 0.9 * 1.9
+        let optimizer = Sgd::with_momentum(0.1, 0.9, true);
+        assert_close(&steps(optimizer, &[0.0], &[&[1.0]]), &[-0.19]);
+
+        let optimizer = Sgd::with_momentum(0.1, 0.9, true);
+        assert_close(&steps(optimizer, &[0.0], &[&[1.0], &[1.0]]), &[-0.19 - 0.271]);
+    }
+}
@@ -0,0 +1,45 @@
+/// xorshift64* generator, so initialisation and shuffling are reproducible
+/// from a seed
+pub(crate) struct Random(u64);
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        // The state must never be zero
+        match seed ^ 0x9E37_79B9_7F4A_7C15 {
+            0 => Self(0x9E37_79B9_7F4A_7C15),
+            state => Self(state),
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform in `[0, 1)`
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub fn uniform(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.unit()
+    }
+
+    pub fn normal(&mut self, mean: f64, deviation: f64) -> f64 {
+        // Box-Muller transform
+        let u = 1.0 - self.unit();
+        let v = self.unit();
+
+        mean + deviation * (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+    }
+
+    /// Fisher-Yates shuffle
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.unit() * (i + 1) as f64) as usize;
+            items.swap(i, j.min(i));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::codegen::ScalarType;
+
+/// Rust primitive types a `Tensor` or `Element` can hold
+pub trait Scalar: Copy + 'static {
+    fn scalar_type() -> ScalarType;
+
+    /// Converts with `as` semantics
+    fn from_f64(value: f64) -> Self;
+
+    fn to_f64(self) -> f64;
+}
+
+impl Scalar for f32 {
+    fn scalar_type() -> ScalarType {
+        ScalarType::F32
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for f64 {
+    fn scalar_type() -> ScalarType {
+        ScalarType::F64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+impl Scalar for u8 {
+    fn scalar_type() -> ScalarType {
+        ScalarType::U8
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as u8
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for u16 {
+    fn scalar_type() -> ScalarType {
+        ScalarType::U16
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as u16
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for u32 {
+    fn scalar_type() -> ScalarType {
+        ScalarType::U32
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as u32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for u64 {
+    fn scalar_type() -> ScalarType {
+        ScalarType::U64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as u64
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for i8 {
+    fn scalar_type() -> ScalarType {
+        ScalarType::I8
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as i8
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for i16 {
+    fn scalar_type() -> ScalarType {
+        ScalarType::I16
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as i16
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for i32 {
+    fn scalar_type() -> ScalarType {
+        ScalarType::I32
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as i32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Scalar for i64 {
+    fn scalar_type() -> ScalarType {
+        ScalarType::I64
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as i64
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
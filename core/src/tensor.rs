@@ -6,91 +6,166 @@ pub struct Tensor<F> {
     buffer: NonNull<F>,
     dimension: crate::Dimension,
     channels: usize,
+    layout: crate::ChannelLayout,
 }
 
-impl<F: Copy> Tensor<F> {
+impl<F> Tensor<F> {
+    fn memory_layout(dimension: &crate::Dimension, channels: usize) -> crate::Result<std::alloc::Layout> {
+        let scalars = dimension.axes()
+            .iter()
+            .try_fold(channels, |scalars, axis| scalars.checked_mul(*axis as usize));
+
+        match scalars {
+            Some(scalars) => std::alloc::Layout::array::<F>(scalars)
+                .or(Errors::InvalidTensorLayout.into()),
+            None => Errors::InvalidTensorLayout.into(),
+        }
+    }
+}
 
-    unsafe fn allocate(dimension: &crate::Dimension) -> crate::Result<NonNull<F>> {
-        use std::alloc::*;
+impl<F: Copy> Tensor<F> {
 
-        let layout = {
-            let align  = std::mem::align_of::<F>();
-            let size = std::mem::size_of::<F>();
+    unsafe fn allocate(dimension: &crate::Dimension, channels: usize) -> crate::Result<NonNull<F>> {
+        let layout = Self::memory_layout(dimension, channels)?;
 
-            Layout::from_size_align(size * 
-                dimension.0 as usize *
-                dimension.1 as usize *
-                dimension.2 as usize, align)
-            .or(Errors::InvalidTensorLayout.into())?
-        };
+        if layout.size() == 0 {
+            return Ok(NonNull::dangling());
+        }
 
-        NonNull::new(alloc(layout) as _)
+        NonNull::new(std::alloc::alloc(layout) as _)
             .ok_or(std::convert::Into::<crate::Error>::into(Errors::TensorAllocationFailed))
     }
 
-    pub fn with_value(value: impl TryInto<F, Error = crate::Error> + crate::ChannelCount + Copy, dimension: crate::Dimension) -> crate::Result<Self> {
+    pub fn with_value(value: impl TryInto<crate::Element<F>, Error = crate::Error> + crate::ChannelCount + Copy, dimension: crate::Dimension) -> crate::Result<Self> {
+        let element: crate::Element<F> = value.try_into()?;
+        let channels = element.channels();
+
+        let mut data = Vec::with_capacity(Self::scalars(&dimension, channels));
+
+        for _ in 0..Self::scalars(&dimension, 1) {
+            data.extend_from_slice(element.as_slice());
+        }
+
+        Self::from_vec(data, dimension, channels)
+    }
+
+    pub fn with_value_from_fn<T>(generator: impl Fn(crate::Dimension) -> T, dimension: crate::Dimension) -> crate::Result<Self>
+        where T: TryInto<crate::Element<F>, Error = crate::Error> + crate::ChannelCount {
+        let mut data = Vec::new();
+        let mut channels = None;
+
+        for index in dimension.indices() {
+            let value = (generator)(index);
+
+            if let Some(channels) = channels {
+                if channels != value.channels() {
+                    return Errors::TensorNonUniformChannel.into();
+                }
+            } else {
+                channels = Some(value.channels())
+            }
+
+            let element: crate::Element<F> = value.try_into()?;
+            data.extend_from_slice(element.as_slice());
+        }
+
+        Self::from_vec(data, dimension, channels.unwrap_or(1))
+    }
+
+    /// Tensor holding `data`, `channels` scalars per element with elements
+    /// ordered along x, then y, then z
+    pub fn from_vec(data: Vec<F>, dimension: crate::Dimension, channels: usize) -> crate::Result<Self> {
+        if channels == 0 || data.len() != Self::scalars(&dimension, channels) {
+            return Errors::InvalidTensorLayout.into();
+        }
+
+        // Boxed slices are allocated with the same layout `Drop` frees with
+        let buffer = NonNull::from(Box::leak(data.into_boxed_slice())).cast();
+
+        Ok(Self {
+            buffer,
+            dimension,
+            channels,
+            layout: crate::ChannelLayout::Interleaved,
+        })
+    }
+
+    pub fn from_slice(data: &[F], dimension: crate::Dimension, channels: usize) -> crate::Result<Self> {
+        if channels == 0 || data.len() != Self::scalars(&dimension, channels) {
+            return Errors::InvalidTensorLayout.into();
+        }
+
         let buffer;
 
         unsafe {
-            buffer = Self::allocate(&dimension)?;
+            buffer = Self::allocate(&dimension, channels)?;
+            buffer.as_ptr().copy_from_nonoverlapping(data.as_ptr(), data.len());
+        }
+
+        Ok(Self {
+            buffer,
+            dimension,
+            channels,
+            layout: crate::ChannelLayout::Interleaved,
+        })
+    }
 
-            let mut current = buffer.as_ptr();
-            let end = current.offset(dimension.0 as isize * dimension.1 as isize * dimension.2 as isize);
+    /// Tensor with every scalar set to `value`
+    pub fn full(value: F, dimension: crate::Dimension, channels: usize) -> crate::Result<Self> {
+        if channels == 0 {
+            return Errors::InvalidTensorLayout.into();
+        }
+
+        let buffer;
 
-            while current < end {
-                current.write(value.try_into()?);
-                current = current.add(1);
+        unsafe {
+            buffer = Self::allocate(&dimension, channels)?;
+
+            for i in 0..Self::scalars(&dimension, channels) {
+                buffer.as_ptr().add(i).write(value);
             }
         }
 
         Ok(Self {
             buffer,
             dimension,
-            channels: value.channels(),
+            channels,
+            layout: crate::ChannelLayout::Interleaved,
         })
     }
 
-    pub fn with_value_from_fn<T>(generator: impl Fn(crate::Dimension) -> T, dimension: crate::Dimension) -> crate::Result<Self>
-        where T: TryInto<F, Error = crate::Error> + crate::ChannelCount {
+    fn scalars(dimension: &crate::Dimension, channels: usize) -> usize {
+        dimension.elements() * channels
+    }
+
+    pub(crate) fn zeroed(dimension: crate::Dimension, channels: usize) -> crate::Result<Self> {
+        if channels == 0 {
+            return Errors::InvalidTensorLayout.into();
+        }
+
         let buffer;
-        let mut channels = None;
 
         unsafe {
-            buffer = Self::allocate(&dimension)?;
-            let ptr = buffer.as_ptr();
-            
-            let w1x2 = dimension.1 as isize * dimension.2 as isize;
-
-            for i2 in 0..dimension.2 {
-                for i1 in 0..dimension.1 {
-                    for i0 in 0..dimension.0 {
-                        let index = i0 as isize
-                            + i1 as isize * dimension.0 as isize
-                            + i2 as isize * w1x2;
-
-                        let value = (generator)(crate::Dimension(i0, i1, i2));
-
-                        if let Some(channels) = channels {
-                            if channels != value.channels() {
-                                return Errors::TensorNonUniformChannel.into();
-                            }
-                        } else {
-                            channels = Some(value.channels())
-                        }
-
-                        ptr.offset(index).write(value.try_into()?);
-                    }
-                }
-            }
+            buffer = Self::allocate(&dimension, channels)?;
+            buffer.as_ptr().write_bytes(0, Self::scalars(&dimension, channels));
         }
 
         Ok(Self {
             buffer,
             dimension,
-            channels: channels.unwrap()
+            channels,
+            layout: crate::ChannelLayout::Interleaved,
         })
     }
 
+    pub(crate) fn as_ptr(&self) -> *const F {
+        self.buffer.as_ptr()
+    }
+
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut F {
+        self.buffer.as_ptr()
+    }
+
     pub fn dimension(&self) -> &crate::Dimension {
         &self.dimension
     }
@@ -98,25 +173,477 @@ impl<F: Copy> Tensor<F> {
     pub fn channels(&self) -> usize {
         self.channels
     }
+
+    pub fn layout(&self) -> crate::ChannelLayout {
+        self.layout
+    }
+
+    /// Rearranges the scalars into `layout`
+    pub fn into_layout(mut self, layout: crate::ChannelLayout) -> Self {
+        if layout == self.layout || self.channels == 1 {
+            self.layout = layout;
+            return self;
+        }
+
+        let data = self.as_slice().to_vec();
+        let elements = self.len() / self.channels;
+        let channels = self.channels;
+        let destination = self.as_mut_slice();
+
+        for element in 0..elements {
+            for channel in 0..channels {
+                match layout {
+                    crate::ChannelLayout::Planar => destination[channel * elements + element] = data[element * channels + channel],
+                    crate::ChannelLayout::Interleaved => destination[element * channels + channel] = data[channel * elements + element],
+                }
+            }
+        }
+
+        self.layout = layout;
+        self
+    }
+
+    /// Reinterprets the tensor as `dimension`, keeping its elements in order
+    pub fn reshape(mut self, dimension: crate::Dimension) -> crate::Result<Self> {
+        if Self::scalars(&dimension, self.channels) != self.len() {
+            return Errors::IncompatibleOperandDimensions.into();
+        }
+
+        self.dimension = dimension;
+        Ok(self)
+    }
+
+    /// Lays every element out along the second axis, the shape `Dense`
+    /// layers take
+    pub fn flatten(self) -> crate::Result<Self> {
+        let elements = self.len() / self.channels;
+        self.reshape(crate::Dimension::new(1, elements as u32, 1))
+    }
+
+    /// Number of scalars held, every element holding `channels` of them
+    pub fn len(&self) -> usize {
+        self.dimension.elements() * self.channels
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every scalar in storage order, elements ordered along x, then y,
+    /// then z within each channel plane for planar tensors
+    pub fn as_slice(&self) -> &[F] {
+        unsafe { std::slice::from_raw_parts(self.buffer.as_ptr(), self.len()) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [F] {
+        unsafe { std::slice::from_raw_parts_mut(self.buffer.as_ptr(), self.len()) }
+    }
+
+    /// Channel `channel` of the element at `index`, whatever the layout
+    pub fn scalar(&self, index: crate::Dimension, channel: usize) -> Option<&F> {
+        let offset = self.scalar_offset(index, channel)?;
+        Some(&self.as_slice()[offset])
+    }
+
+    pub fn scalar_mut(&mut self, index: crate::Dimension, channel: usize) -> Option<&mut F> {
+        let offset = self.scalar_offset(index, channel)?;
+        Some(&mut self.as_mut_slice()[offset])
+    }
+
+    /// Channels of the element at `index`, or `None` if it is out of bounds
+    /// or the tensor is planar. The views of elements below are only
+    /// available for interleaved tensors, whose channels lie next to each
+    /// other; `scalar` reads a single channel of either layout.
+    pub fn get(&self, index: crate::Dimension) -> Option<&[F]> {
+        let offset = self.offset(index)?;
+        Some(&self.as_slice()[offset..offset + self.channels])
+    }
+
+    pub fn get_mut(&mut self, index: crate::Dimension) -> Option<&mut [F]> {
+        let offset = self.offset(index)?;
+        let channels = self.channels;
+
+        Some(&mut self.as_mut_slice()[offset..offset + channels])
+    }
+
+    /// Elements `(0..x, y, z)`, which lie next to each other. Here and
+    /// below, `z` counts planes over every axis from the third on.
+    pub fn column(&self, y: u32, z: u32) -> Option<&[F]> {
+        let offset = self.plane_offset(0, y, z)?;
+        Some(&self.as_slice()[offset..offset + self.dimension.x() as usize * self.channels])
+    }
+
+    /// Elements `(x, 0..y, z)`
+    pub fn row(&self, x: u32, z: u32) -> Option<impl Iterator<Item = &[F]>> {
+        let offset = self.plane_offset(x, 0, z)?;
+
+        Some(self.as_slice()[offset..]
+            .chunks(self.channels)
+            .step_by(self.dimension.x() as usize)
+            .take(self.dimension.y() as usize))
+    }
+
+    /// Elements `(0..x, 0..y, z)`
+    pub fn plane(&self, z: u32) -> Option<&[F]> {
+        let offset = self.plane_offset(0, 0, z)?;
+        let length = self.dimension.x() as usize * self.dimension.y() as usize * self.channels;
+
+        Some(&self.as_slice()[offset..offset + length])
+    }
+
+    /// Channels of every element, in the order of `as_slice`
+    pub fn elements(&self) -> Option<std::slice::Chunks<'_, F>> {
+        self.interleaved()?;
+        Some(self.as_slice().chunks(self.channels))
+    }
+
+    pub fn elements_mut(&mut self) -> Option<std::slice::ChunksMut<'_, F>> {
+        self.interleaved()?;
+
+        let channels = self.channels;
+        Some(self.as_mut_slice().chunks_mut(channels))
+    }
+
+    /// Every element along with its index
+    pub fn indexed(&self) -> Option<impl Iterator<Item = (crate::Dimension, &[F])>> {
+        let dimension = self.dimension;
+
+        Some(self.elements()?.enumerate().map(move |(i, element)| (dimension.index_at(i), element)))
+    }
+
+    /// A single channel of every element, whatever the layout
+    pub fn channel(&self, channel: usize) -> impl Iterator<Item = &F> {
+        let channels = self.channels;
+        let elements = self.len() / channels;
+
+        let (start, step) = match self.layout {
+            crate::ChannelLayout::Interleaved => (channel, channels),
+            crate::ChannelLayout::Planar => (channel * elements, 1),
+        };
+
+        let count = if channel < channels { elements } else { 0 };
+
+        self.as_slice().iter().skip(start).step_by(step).take(count)
+    }
+
+    /// Offset of the first scalar of the element at `index` of an
+    /// interleaved tensor
+    fn offset(&self, index: crate::Dimension) -> Option<usize> {
+        self.interleaved()?;
+        Some(self.element(index)? * self.channels)
+    }
+
+    fn scalar_offset(&self, index: crate::Dimension, channel: usize) -> Option<usize> {
+        if channel >= self.channels {
+            return None;
+        }
+
+        let element = self.element(index)?;
+
+        Some(match self.layout {
+            crate::ChannelLayout::Interleaved => element * self.channels + channel,
+            crate::ChannelLayout::Planar => channel * (self.len() / self.channels) + element,
+        })
+    }
+
+    /// Position of the element at `index` among all elements
+    fn element(&self, index: crate::Dimension) -> Option<usize> {
+        self.dimension.position(&index)
+    }
+
+    /// Offset of the first scalar of the element `(x, y)` of plane `z` of
+    /// an interleaved tensor
+    fn plane_offset(&self, x: u32, y: u32, z: u32) -> Option<usize> {
+        let (width, height) = (self.dimension.x(), self.dimension.y());
+
+        if x >= width || y >= height || z >= self.dimension.z() {
+            return None;
+        }
+
+        self.interleaved()?;
+
+        let element = x as usize + y as usize * width as usize + z as usize * width as usize * height as usize;
+        Some(element * self.channels)
+    }
+
+    fn interleaved(&self) -> Option<()> {
+        match self.layout {
+            crate::ChannelLayout::Interleaved => Some(()),
+            crate::ChannelLayout::Planar => None,
+        }
+    }
 }
 
-impl<F: PartialEq> PartialEq for Tensor<F> {
+impl<F: crate::Scalar> Tensor<F> {
+    pub fn zeros(dimension: crate::Dimension, channels: usize) -> crate::Result<Self> {
+        Self::full(F::from_f64(0.0), dimension, channels)
+    }
+
+    pub fn ones(dimension: crate::Dimension, channels: usize) -> crate::Result<Self> {
+        Self::full(F::from_f64(1.0), dimension, channels)
+    }
+}
+
+/// Collects scalars into a single column of single channel elements
+impl<F: Copy> FromIterator<F> for Tensor<F> {
+    /// # Panics
+    /// If there are more than `u32::MAX` scalars
+    fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> Self {
+        let data: Vec<F> = iter.into_iter().collect();
+        let length = u32::try_from(data.len()).expect("More scalars than a tensor axis can hold");
+
+        Self::from_vec(data, crate::Dimension::new(length, 1, 1), 1).expect("Invalid tensor layout")
+    }
+}
+
+impl<F: Copy> std::ops::Index<crate::Dimension> for Tensor<F> {
+    type Output = [F];
+
+    /// # Panics
+    /// If `index` is out of bounds or the tensor is planar, see `get`
+    fn index(&self, index: crate::Dimension) -> &[F] {
+        self.get(index).expect("Tensor index out of bounds or element not contiguous")
+    }
+}
+
+impl<F: Copy> std::ops::IndexMut<crate::Dimension> for Tensor<F> {
+    /// # Panics
+    /// If `index` is out of bounds or the tensor is planar, see `get`
+    fn index_mut(&mut self, index: crate::Dimension) -> &mut [F] {
+        self.get_mut(index).expect("Tensor index out of bounds or element not contiguous")
+    }
+}
+
+impl<F: Copy> Clone for Tensor<F> {
+    fn clone(&self) -> Self {
+        let buffer = unsafe { Self::allocate(&self.dimension, self.channels) }
+            .expect("Tensor allocation failed");
+
+        unsafe {
+            buffer.as_ptr().copy_from_nonoverlapping(self.buffer.as_ptr(), self.len());
+        }
+
+        Self {
+            buffer,
+            dimension: self.dimension,
+            channels: self.channels,
+            layout: self.layout,
+        }
+    }
+}
+
+/// Tensors holding the same elements are equal whatever their layouts
+impl<F: PartialEq + Copy> PartialEq for Tensor<F> {
     fn eq(&self, other: &Self) -> bool {
-        let mut a = self.buffer;
-        let mut b = other.buffer;
-        let end = unsafe { self.buffer.as_ptr().add(self.channels) };
+        if self.dimension != other.dimension || self.channels != other.channels {
+            return false;
+        }
+
+        if self.layout == other.layout {
+            return self.as_slice() == other.as_slice();
+        }
 
-        while a.as_ptr() < end {
+        (0..self.channels).all(|channel| self.channel(channel).eq(other.channel(channel)))
+    }
+}
+
+impl<F> Drop for Tensor<F> {
+    fn drop(&mut self) {
+        // The layout was valid when the buffer was allocated
+        let layout = Self::memory_layout(&self.dimension, self.channels)
+            .expect("Tensor layout changed since allocation");
+
+        if layout.size() != 0 {
             unsafe {
-                if F::ne(a.as_ref(), b.as_ref()) {
-                    return false;
-                }
+                std::alloc::dealloc(self.buffer.as_ptr() as _, layout);
             }
+        }
+    }
+}
+
+// The buffer is owned exclusively, like a `Box<[F]>`
+unsafe impl<F: Send> Send for Tensor<F> {}
+unsafe impl<F: Sync> Sync for Tensor<F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::Tensor;
+    use crate::{ ChannelLayout, Dimension };
+
+    /// 3x2 tensor of two channels, element `i` holding `(i, 10 + i)`
+    fn sample() -> Tensor<i32> {
+        let data = (0..6).flat_map(|i| [i, 10 + i]).collect();
+        Tensor::from_vec(data, Dimension::new(3, 2, 1), 2).unwrap()
+    }
+
+    #[test]
+    fn elements_of_interleaved_tensors() {
+        let mut tensor = sample();
+
+        assert_eq!(&tensor[Dimension::new(1, 1, 0)], &[4, 14]);
+        assert_eq!(tensor.get(Dimension::new(2, 0, 0)), Some(&[2, 12][..]));
+        assert_eq!(tensor.get(Dimension::new(3, 0, 0)), None);
+
+        tensor[Dimension::new(0, 1, 0)][1] = 99;
+        assert_eq!(tensor.scalar(Dimension::new(0, 1, 0), 1), Some(&99));
+        assert_eq!(tensor.column(1, 0), Some(&[3, 99, 4, 14, 5, 15][..]));
+        assert_eq!(tensor.row(2, 0).unwrap().collect::<Vec<_>>(), vec![&[2, 12][..], &[5, 15][..]]);
+    }
+
+    #[test]
+    fn elements_of_planar_tensors() {
+        let mut tensor = sample().into_layout(ChannelLayout::Planar);
+
+        assert_eq!(tensor.as_slice(), &[0, 1, 2, 3, 4, 5, 10, 11, 12, 13, 14, 15]);
+        assert_eq!(tensor.get(Dimension::new(1, 1, 0)), None);
+        assert!(tensor.get_mut(Dimension::new(1, 1, 0)).is_none());
+        assert!(tensor.elements().is_none());
+
+        assert_eq!(tensor.scalar(Dimension::new(1, 1, 0), 0), Some(&4));
+        assert_eq!(tensor.scalar(Dimension::new(1, 1, 0), 1), Some(&14));
+        assert_eq!(tensor.scalar(Dimension::new(1, 1, 0), 2), None);
+
+        *tensor.scalar_mut(Dimension::new(2, 0, 0), 1).unwrap() = 99;
+        assert_eq!(tensor.channel(1).copied().collect::<Vec<_>>(), vec![10, 11, 99, 13, 14, 15]);
+
+        let tensor = tensor.into_layout(ChannelLayout::Interleaved);
+        assert_eq!(&tensor[Dimension::new(2, 0, 0)], &[2, 99]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not contiguous")]
+    fn indexing_planar_tensors_panics() {
+        let tensor = sample().into_layout(ChannelLayout::Planar);
+        let _ = &tensor[Dimension::new(0, 0, 0)];
+    }
+
+    fn message<T>(result: crate::Result<T>) -> String {
+        match result {
+            Ok(_) => String::from("Ok"),
+            Err(error) => format!("{:?}", error),
+        }
+    }
+
+    #[test]
+    fn from_vec_checks_length() {
+        let dimension = Dimension::new(2, 2, 1);
+
+        assert!(Tensor::from_vec(vec![1.0f32; 8], dimension, 2).is_ok());
+        assert_eq!(message(Tensor::from_vec(vec![1.0f32; 7], dimension, 2)), "Invalid tensor layout");
+        assert_eq!(message(Tensor::from_vec(vec![1.0f32; 9], dimension, 2)), "Invalid tensor layout");
+        assert_eq!(message(Tensor::from_vec(Vec::<f32>::new(), dimension, 0)), "Invalid tensor layout");
+    }
+
+    #[test]
+    fn from_vec_keeps_the_data() {
+        let mut data = Vec::with_capacity(16);
+        data.extend([1u16, 2, 3, 4, 5, 6]);
+
+        let tensor = Tensor::from_vec(data, Dimension::new(3, 1, 1), 2).unwrap();
+        assert_eq!(tensor.as_slice(), &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(tensor.layout(), ChannelLayout::Interleaved);
+
+        let empty = Tensor::<u16>::from_vec(Vec::new(), Dimension::new(0, 4, 1), 3).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn invalid_layouts() {
+        let dimension = Dimension::new(2, 1, 1);
 
-            a = unsafe { NonNull::new_unchecked( a.as_ptr().add(1)) };
-            b = unsafe { NonNull::new_unchecked( b.as_ptr().add(1)) };
+        assert_eq!(message(Tensor::from_slice(&[1i8, 2, 3], dimension, 1)), "Invalid tensor layout");
+        assert_eq!(message(Tensor::from_slice(&[1i8, 2], dimension, 0)), "Invalid tensor layout");
+        assert_eq!(message(Tensor::full(1i8, dimension, 0)), "Invalid tensor layout");
+        assert_eq!(message(Tensor::<i8>::zeros(dimension, 0)), "Invalid tensor layout");
+        assert_eq!(message(Tensor::<u64>::zeros(Dimension::new(u32::MAX, u32::MAX, u32::MAX), 4)), "Invalid tensor layout");
+    }
+
+    #[test]
+    fn collects_a_column() {
+        let tensor: Tensor<f64> = (0..5).map(|i| i as f64 * 0.5).collect();
+
+        assert_eq!(tensor.dimension(), &Dimension::new(5, 1, 1));
+        assert_eq!(tensor.channels(), 1);
+        assert_eq!(tensor.as_slice(), &[0.0, 0.5, 1.0, 1.5, 2.0]);
+
+        let empty: Tensor<f64> = std::iter::empty().collect();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn equality_ignores_layout() {
+        let interleaved = sample();
+        let planar = sample().into_layout(ChannelLayout::Planar);
+
+        assert!(interleaved == planar);
+        assert!(planar == interleaved);
+        assert!(planar == planar.clone());
+
+        let mut different = planar.clone();
+        *different.scalar_mut(Dimension::new(2, 1, 0), 1).unwrap() = 0;
+        assert!(interleaved != different);
+
+        let reshaped = sample().reshape(Dimension::new(2, 3, 1)).unwrap();
+        assert!(interleaved != reshaped);
+    }
+
+    #[test]
+    fn clones_own_their_buffers() {
+        for layout in [ChannelLayout::Interleaved, ChannelLayout::Planar] {
+            let original = sample().into_layout(layout);
+            let mut copy = original.clone();
+
+            assert!(copy == original);
+            assert_eq!(copy.layout(), layout);
+            assert_ne!(copy.as_slice().as_ptr(), original.as_slice().as_ptr());
+
+            *copy.scalar_mut(Dimension::new(0, 0, 0), 1).unwrap() = -1;
+            assert_eq!(original.scalar(Dimension::new(0, 0, 0), 1), Some(&10));
+
+            drop(original);
+            assert_eq!(copy.scalar(Dimension::new(1, 0, 0), 1), Some(&11));
         }
+    }
+
+    #[test]
+    fn zero_sized_tensors() {
+        for dimension in [Dimension::new(0, 1, 1), Dimension::new(3, 0, 2), Dimension::new(1, 1, 0)] {
+            let tensor = Tensor::<f32>::zeros(dimension, 4).unwrap();
+            let copy = tensor.clone().into_layout(ChannelLayout::Planar);
+
+            assert!(tensor.is_empty());
+            assert!(copy == tensor);
+            assert_eq!(copy.channel(3).count(), 0);
+        }
+
+        // Zero sized scalars never allocate either
+        let units = Tensor::from_vec(vec![(); 6], Dimension::new(3, 2, 1), 1).unwrap();
+        assert_eq!(units.clone().len(), 6);
+    }
+
+    #[test]
+    fn multi_channel_allocations() {
+        let tensor = Tensor::full(7u8, Dimension::new(5, 3, 2), 4).unwrap();
+        assert_eq!(tensor.len(), 120);
 
-        true
+        let planar = tensor.clone().into_layout(ChannelLayout::Planar);
+        let back = planar.clone().into_layout(ChannelLayout::Interleaved);
+
+        assert!(back == tensor);
+        assert!(planar == back);
+        assert!(planar.as_slice().iter().all(|scalar| *scalar == 7));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn moves_between_threads() {
+        fn shared<T: Send + Sync>(_: &T) {}
+
+        let tensor = sample().into_layout(ChannelLayout::Planar);
+        shared(&tensor);
+
+        let copy = tensor.clone();
+        let total = std::thread::spawn(move || copy.as_slice().iter().sum::<i32>()).join().unwrap();
+
+        assert_eq!(total, tensor.as_slice().iter().sum::<i32>());
+    }
+}
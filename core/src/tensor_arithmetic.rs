@@ -0,0 +1,432 @@
+use crate::{ Tensor, Element, Dimension, Errors };
+
+impl<F: Copy> Tensor<F> {
+    /// Applies `f` to every scalar, keeping the layout
+    pub fn map(&self, f: impl Fn(F) -> F) -> Self {
+        let mut result = self.clone();
+        result.as_mut_slice().iter_mut().for_each(|scalar| *scalar = f(*scalar));
+        result
+    }
+
+    /// Combines both tensors scalar by scalar with `f`. Along every axis the
+    /// sizes must match or one of them be 1, in which case that tensor is
+    /// repeated along the axis; the same goes for the channel counts. The
+    /// result is interleaved.
+    pub fn zip_with(&self, other: &Self, f: impl Fn(F, F) -> F) -> crate::Result<Self> {
+        let a = *self.dimension();
+        let b = *other.dimension();
+
+        // Axes past the rank of either tensor are of size 1
+        let axes = (0..a.rank().max(b.rank()))
+            .map(|axis| broadcast(a.axis(axis), b.axis(axis)))
+            .collect::<Option<Vec<u32>>>()
+            .ok_or(crate::Error::from(Errors::IncompatibleOperandDimensions))?;
+
+        let dimension = Dimension::from_axes(&axes)?;
+
+        let channels = match (self.channels(), other.channels()) {
+            (a, b) if a == b || b == 1 => a,
+            (1, b) => b,
+            _ => return Errors::DifferentChannelCounts.into(),
+        };
+
+        let (a_axes, a_channel) = self.broadcast_strides(&dimension)?;
+        let (b_axes, b_channel) = other.broadcast_strides(&dimension)?;
+        let (a_scalars, b_scalars) = (self.as_slice(), other.as_slice());
+
+        let mut data = Vec::with_capacity(dimension.elements() * channels);
+        let mut index = vec![0; axes.len()];
+        let (mut a_offset, mut b_offset) = (0, 0);
+
+        for _ in 0..dimension.elements() {
+            for channel in 0..channels {
+                data.push(f(a_scalars[a_offset + channel * a_channel], b_scalars[b_offset + channel * b_channel]));
+            }
+
+            // Steps to the next index, rewinding every axis that wraps
+            for axis in 0..axes.len() {
+                index[axis] += 1;
+                a_offset += a_axes[axis];
+                b_offset += b_axes[axis];
+
+                if index[axis] < axes[axis] as usize {
+                    break;
+                }
+
+                a_offset -= a_axes[axis] * index[axis];
+                b_offset -= b_axes[axis] * index[axis];
+                index[axis] = 0;
+            }
+        }
+
+        Self::from_vec(data, dimension, channels)
+    }
+
+    /// Steps between the scalars read for consecutive indices along every
+    /// axis of `dimension` and for consecutive channels, which are 0 where
+    /// this tensor is of size 1 and so repeated
+    fn broadcast_strides(&self, dimension: &Dimension) -> crate::Result<(Vec<usize>, usize)> {
+        let (mut stride, channel) = match self.layout() {
+            crate::ChannelLayout::Interleaved => (self.channels(), 1),
+            crate::ChannelLayout::Planar => (1, self.dimension().elements()),
+        };
+
+        let mut axes = Vec::with_capacity(dimension.rank());
+
+        for axis in 0..dimension.rank() {
+            let size = self.dimension().axis(axis);
+
+            axes.push(match size {
+                1 => 0,
+                size if size == dimension.axis(axis) => stride,
+                _ => return Errors::IncompatibleOperandDimensions.into(),
+            });
+
+            stride *= size as usize;
+        }
+
+        Ok((axes, if self.channels() == 1 { 0 } else { channel }))
+    }
+
+    /// Index and channel of the greatest scalar, the first one on ties.
+    /// Unordered scalars such as NaN are skipped.
+    pub fn argmax(&self) -> Option<(Dimension, usize)> where F: PartialOrd {
+        let dimension = *self.dimension();
+        let mut best: Option<((Dimension, usize), F)> = None;
+
+        for index in dimension.indices() {
+            for channel in 0..self.channels() {
+                let scalar = *self.scalar(index, channel)?;
+
+                // NaN is not even equal to itself
+                let greater = match &best {
+                    Some((_, max)) => scalar > *max,
+                    None => scalar.partial_cmp(&scalar).is_some(),
+                };
+
+                if greater {
+                    best = Some(((index, channel), scalar));
+                }
+            }
+        }
+
+        best.map(|(position, _)| position)
+    }
+
+    /// Greatest scalar, skipping unordered ones such as NaN
+    pub fn max(&self) -> Option<F> where F: PartialOrd {
+        let (index, channel) = self.argmax()?;
+        self.scalar(index, channel).copied()
+    }
+}
+
+impl<F: crate::Scalar + std::ops::Add<Output = F>> Tensor<F> {
+    /// Sum of every scalar
+    pub fn sum(&self) -> F {
+        self.as_slice()
+            .iter()
+            .fold(F::from_f64(0.0), |sum, scalar| sum + *scalar)
+    }
+
+    /// Mean of every scalar, computed in `f64`
+    pub fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.as_slice().iter().map(|scalar| scalar.to_f64()).sum::<f64>() / self.len() as f64)
+    }
+}
+
+fn broadcast(a: u32, b: u32) -> Option<u32> {
+    match (a, b) {
+        (a, b) if a == b || b == 1 => Some(a),
+        (1, b) => Some(b),
+        _ => None,
+    }
+}
+
+/// Tensor of a single element holding the channels of `element`
+fn element_tensor<F: Copy>(element: &Element<F>) -> Tensor<F> {
+    Tensor::from_slice(element.as_slice(), Dimension::new(1, 1, 1), element.channels())
+        .expect("Different channel counts")
+}
+
+impl<F: Copy + std::ops::Add<Output = F>> std::ops::Add for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    /// # Panics
+    /// If the dimensions or channel counts can't be broadcast together
+    fn add(self, rhs: Self) -> Tensor<F> {
+        self.zip_with(rhs, |a, b| a + b).expect("Incompatible operand dimensions")
+    }
+}
+
+impl<F: Copy + std::ops::Sub<Output = F>> std::ops::Sub for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    /// # Panics
+    /// If the dimensions or channel counts can't be broadcast together
+    fn sub(self, rhs: Self) -> Tensor<F> {
+        self.zip_with(rhs, |a, b| a - b).expect("Incompatible operand dimensions")
+    }
+}
+
+/// Element-wise product, unlike `Value::multiply`
+impl<F: Copy + std::ops::Mul<Output = F>> std::ops::Mul for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    /// # Panics
+    /// If the dimensions or channel counts can't be broadcast together
+    fn mul(self, rhs: Self) -> Tensor<F> {
+        self.zip_with(rhs, |a, b| a * b).expect("Incompatible operand dimensions")
+    }
+}
+
+impl<F: Copy + std::ops::Div<Output = F>> std::ops::Div for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    /// # Panics
+    /// If the dimensions or channel counts can't be broadcast together
+    fn div(self, rhs: Self) -> Tensor<F> {
+        self.zip_with(rhs, |a, b| a / b).expect("Incompatible operand dimensions")
+    }
+}
+
+impl<F: Copy + std::ops::Add<Output = F>> std::ops::Add for Tensor<F> {
+    type Output = Tensor<F>;
+
+    fn add(self, rhs: Self) -> Tensor<F> {
+        &self + &rhs
+    }
+}
+
+impl<F: Copy + std::ops::Sub<Output = F>> std::ops::Sub for Tensor<F> {
+    type Output = Tensor<F>;
+
+    fn sub(self, rhs: Self) -> Tensor<F> {
+        &self - &rhs
+    }
+}
+
+impl<F: Copy + std::ops::Mul<Output = F>> std::ops::Mul for Tensor<F> {
+    type Output = Tensor<F>;
+
+    fn mul(self, rhs: Self) -> Tensor<F> {
+        &self * &rhs
+    }
+}
+
+impl<F: Copy + std::ops::Div<Output = F>> std::ops::Div for Tensor<F> {
+    type Output = Tensor<F>;
+
+    fn div(self, rhs: Self) -> Tensor<F> {
+        &self / &rhs
+    }
+}
+
+impl<F: Copy + std::ops::Add<Output = F>> std::ops::Add<&Element<F>> for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    /// # Panics
+    /// If the channel counts can't be broadcast together
+    fn add(self, rhs: &Element<F>) -> Tensor<F> {
+        self + &element_tensor(rhs)
+    }
+}
+
+impl<F: Copy + std::ops::Sub<Output = F>> std::ops::Sub<&Element<F>> for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    /// # Panics
+    /// If the channel counts can't be broadcast together
+    fn sub(self, rhs: &Element<F>) -> Tensor<F> {
+        self - &element_tensor(rhs)
+    }
+}
+
+impl<F: Copy + std::ops::Mul<Output = F>> std::ops::Mul<&Element<F>> for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    /// # Panics
+    /// If the channel counts can't be broadcast together
+    fn mul(self, rhs: &Element<F>) -> Tensor<F> {
+        self * &element_tensor(rhs)
+    }
+}
+
+impl<F: Copy + std::ops::Div<Output = F>> std::ops::Div<&Element<F>> for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    /// # Panics
+    /// If the channel counts can't be broadcast together
+    fn div(self, rhs: &Element<F>) -> Tensor<F> {
+        self / &element_tensor(rhs)
+    }
+}
+
+impl<F: crate::Scalar + std::ops::Add<Output = F>> std::ops::Add<F> for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    fn add(self, rhs: F) -> Tensor<F> {
+        self.map(|a| a + rhs)
+    }
+}
+
+impl<F: crate::Scalar + std::ops::Sub<Output = F>> std::ops::Sub<F> for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    fn sub(self, rhs: F) -> Tensor<F> {
+        self.map(|a| a - rhs)
+    }
+}
+
+impl<F: crate::Scalar + std::ops::Mul<Output = F>> std::ops::Mul<F> for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    fn mul(self, rhs: F) -> Tensor<F> {
+        self.map(|a| a * rhs)
+    }
+}
+
+impl<F: crate::Scalar + std::ops::Div<Output = F>> std::ops::Div<F> for &Tensor<F> {
+    type Output = Tensor<F>;
+
+    fn div(self, rhs: F) -> Tensor<F> {
+        self.map(|a| a / rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ ChannelLayout, Dimension, Element, Tensor };
+
+    fn tensor(data: &[f32], axes: &[u32], channels: usize) -> Tensor<f32> {
+        Tensor::from_slice(data, Dimension::from_axes(axes).unwrap(), channels).unwrap()
+    }
+
+    #[test]
+    fn broadcasts_along_the_second_axis() {
+        let a = tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[3, 2], 1);
+        let b = tensor(&[10.0, 20.0, 30.0], &[3, 1], 1);
+
+        let sum = &a + &b;
+        assert_eq!(sum.dimension().axes(), &[3, 2]);
+        assert_eq!(sum.as_slice(), &[11.0, 22.0, 33.0, 14.0, 25.0, 36.0]);
+
+        let row = tensor(&[1.0, 2.0], &[1, 2], 1);
+        assert_eq!((&b * &row).as_slice(), &[10.0, 20.0, 30.0, 20.0, 40.0, 60.0]);
+    }
+
+    #[test]
+    fn broadcasts_planar_tensors_along_inner_axes() {
+        let a = tensor(&(0..12).map(|i| i as f32).collect::<Vec<_>>(), &[2, 1, 3], 2).into_layout(ChannelLayout::Planar);
+        let b = tensor(&[100.0, 200.0, 300.0, 400.0], &[1, 2, 1], 2).into_layout(ChannelLayout::Planar);
+
+        let sum = &a + &b;
+        assert_eq!(sum.dimension().axes(), &[2, 2, 3]);
+
+        for index in sum.dimension().indices() {
+            let (x, y, z) = (index[0], index[1], index[2]);
+
+            for channel in 0..2 {
+                let expected = a.scalar(Dimension::from_axes(&[x, 0, z]).unwrap(), channel).unwrap()
+                    + b.scalar(Dimension::from_axes(&[0, y, 0]).unwrap(), channel).unwrap();
+                assert_eq!(sum.scalar(index, channel), Some(&expected));
+            }
+        }
+    }
+
+    #[test]
+    fn broadcasts_across_ranks() {
+        let a = tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 1, 1, 2], 1);
+        let b = tensor(&[10.0, 20.0], &[2], 1);
+
+        let sum = &a + &b;
+        assert_eq!(sum.dimension().axes(), &[2, 1, 1, 2]);
+        assert_eq!(sum.as_slice(), &[11.0, 22.0, 13.0, 24.0]);
+        assert_eq!(b.zip_with(&a, |x, y| y - x).unwrap().as_slice(), &[-9.0, -18.0, -7.0, -16.0]);
+    }
+
+    #[test]
+    fn rejects_incompatible_dimensions() {
+        let a = tensor(&[1.0, 2.0, 3.0], &[3], 1);
+        let b = tensor(&[1.0, 2.0], &[2], 1);
+        let c = tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[3], 2);
+        let d = tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0], &[3], 3);
+
+        assert!(a.zip_with(&b, |x, y| x + y).is_err());
+        assert!(c.zip_with(&d, |x, y| x + y).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Incompatible operand dimensions")]
+    fn operators_panic_on_incompatible_dimensions() {
+        let _ = &tensor(&[1.0, 2.0, 3.0], &[3], 1) + &tensor(&[1.0, 2.0], &[2], 1);
+    }
+
+    #[test]
+    fn broadcasts_channels() {
+        let colors = tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2], 3);
+        let gray = tensor(&[10.0, 100.0], &[2], 1);
+
+        let product = &colors * &gray;
+        assert_eq!(product.channels(), 3);
+        assert_eq!(product.as_slice(), &[10.0, 20.0, 30.0, 400.0, 500.0, 600.0]);
+        assert_eq!((&gray * &colors).as_slice(), product.as_slice());
+
+        // Planar operands broadcast by element too, into an interleaved result
+        let planar = colors.clone().into_layout(ChannelLayout::Planar);
+        let product = &planar * &gray;
+        assert_eq!(product.layout(), ChannelLayout::Interleaved);
+        assert_eq!(product.as_slice(), &[10.0, 20.0, 30.0, 400.0, 500.0, 600.0]);
+    }
+
+    #[test]
+    fn element_and_scalar_operands() {
+        let a = tensor(&[1.0, 2.0, 3.0, 4.0], &[2], 2);
+        let element = Element::from([10.0, 20.0]);
+
+        assert_eq!((&a + &element).as_slice(), &[11.0, 22.0, 13.0, 24.0]);
+        assert_eq!((&a - &element).as_slice(), &[-9.0, -18.0, -7.0, -16.0]);
+        assert_eq!((&a * &element).as_slice(), &[10.0, 40.0, 30.0, 80.0]);
+        assert_eq!((&a / &Element::from([2.0])).as_slice(), &[0.5, 1.0, 1.5, 2.0]);
+
+        assert_eq!((&a + 1.0).as_slice(), &[2.0, 3.0, 4.0, 5.0]);
+        assert_eq!((&a - 1.0).as_slice(), &[0.0, 1.0, 2.0, 3.0]);
+        assert_eq!((&a * 2.0).as_slice(), &[2.0, 4.0, 6.0, 8.0]);
+        assert_eq!((&a / 4.0).as_slice(), &[0.25, 0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn element_operands_need_broadcastable_channels() {
+        let _ = &tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2], 3) + &Element::from([1.0, 2.0]);
+    }
+
+    #[test]
+    fn argmax_skips_nan() {
+        let a = tensor(&[f32::NAN, 1.0, 5.0, f32::NAN, 5.0, 2.0], &[3], 2);
+        assert_eq!(a.argmax().map(|(index, channel)| (index.axes().to_vec(), channel)), Some((vec![1], 0)));
+        assert_eq!(a.max(), Some(5.0));
+
+        let planar = a.clone().into_layout(ChannelLayout::Planar);
+        assert_eq!(planar.argmax().map(|(index, channel)| (index.axes().to_vec(), channel)), Some((vec![1], 0)));
+
+        let nan = tensor(&[f32::NAN, f32::NAN], &[2], 1);
+        assert!(nan.argmax().is_none());
+        assert!(nan.max().is_none());
+
+        let empty = tensor(&[], &[0], 1);
+        assert!(empty.argmax().is_none());
+    }
+
+    #[test]
+    fn sum_and_mean() {
+        let a = tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2], 1);
+
+        assert_eq!(a.sum(), 10.0);
+        assert_eq!(a.mean(), Some(2.5));
+        assert_eq!(tensor(&[], &[0], 1).mean(), None);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::{ Tensor, Dimension, Errors };
+
+impl<F: Copy> Tensor<F> {
+    /// Swaps the first two axes. The result is interleaved, as are those of
+    /// every method below.
+    pub fn transpose(&self) -> crate::Result<Self> {
+        let mut axes: Vec<u32> = (0..self.dimension().rank().max(2) as u32).collect();
+        axes.swap(0, 1);
+
+        // A single axis becomes the second one
+        match self.dimension().rank() {
+            1 => self.clone().reshape(self.dimension().with_axis(1, 1)?)?.permute(&axes),
+            _ => self.permute(&axes),
+        }
+    }
+
+    /// Reorders the axes, axis `i` of the result being axis `axes[i]` of
+    /// this tensor
+    pub fn permute(&self, axes: &[u32]) -> crate::Result<Self> {
+        let dimension = self.dimension().permute(axes)?;
+        let mut data = Vec::with_capacity(self.len());
+
+        for index in dimension.indices() {
+            let mut source = vec![0; axes.len()];
+
+            for (k, axis) in axes.iter().enumerate() {
+                source[*axis as usize] = index[k];
+            }
+
+            self.push_element(Dimension::from_axes(&source)?, &mut data)?;
+        }
+
+        Self::from_vec(data, dimension, self.channels())
+    }
+
+    /// Joins tensors along `axis`, their other axes and channel counts
+    /// having to match
+    pub fn concat(tensors: &[&Self], axis: u32) -> crate::Result<Self> {
+        let axis = axis as usize;
+        let first = tensors.first().ok_or(crate::Error::from(Errors::RequiresTensor))?;
+        let mut dimension = first.dimension().with_axis(axis, 0)?;
+
+        for tensor in tensors {
+            let other = tensor.dimension();
+
+            if tensor.channels() != first.channels() {
+                return Errors::DifferentChannelCounts.into();
+            }
+
+            let rank = dimension.rank().max(other.rank());
+
+            if (0..rank).any(|i| i != axis && other.axis(i) != dimension.axis(i)) {
+                return Errors::IncompatibleOperandDimensions.into();
+            }
+
+            dimension = dimension.with_axis(axis, dimension.axis(axis) + other.axis(axis))?;
+        }
+
+        let mut data = Vec::with_capacity(dimension.elements() * first.channels());
+
+        for index in dimension.indices() {
+            let mut position = index.axis(axis);
+            let mut tensor = 0;
+
+            // Skips the tensors that end before the index
+            while position >= tensors[tensor].dimension().axis(axis) {
+                position -= tensors[tensor].dimension().axis(axis);
+                tensor += 1;
+            }
+
+            tensors[tensor].push_element(index.with_axis(axis, position)?, &mut data)?;
+        }
+
+        Self::from_vec(data, dimension, first.channels())
+    }
+
+    /// Joins tensors of the same dimension along a new `axis`, the axes from
+    /// `axis` on moving one further
+    pub fn stack(tensors: &[&Self], axis: u32) -> crate::Result<Self> {
+        let mut reshaped = Vec::with_capacity(tensors.len());
+
+        for tensor in tensors {
+            let dimension = tensor.dimension().insert_axis(axis as usize, 1)?;
+            reshaped.push((*tensor).clone().reshape(dimension)?);
+        }
+
+        Self::concat(&reshaped.iter().collect::<Vec<_>>(), axis)
+    }
+
+    /// Cuts the tensor along `axis` into consecutive parts of `sizes`, which
+    /// have to add up to the length of the axis
+    pub fn split(&self, axis: u32, sizes: &[u32]) -> crate::Result<Vec<Self>> {
+        let axis = axis as usize;
+        let length = *self.dimension().axes().get(axis).ok_or(crate::Error::from(Errors::InvalidAxis))?;
+
+        if sizes.iter().map(|size| *size as u64).sum::<u64>() != length as u64 {
+            return Errors::IncompatibleOperandDimensions.into();
+        }
+
+        let mut start = 0;
+        let mut parts = Vec::with_capacity(sizes.len());
+
+        for size in sizes {
+            let dimension = self.dimension().with_axis(axis, *size)?;
+            let mut data = Vec::with_capacity(dimension.elements() * self.channels());
+
+            for index in dimension.indices() {
+                self.push_element(index.with_axis(axis, index[axis] + start)?, &mut data)?;
+            }
+
+            parts.push(Self::from_vec(data, dimension, self.channels())?);
+            start += size;
+        }
+
+        Ok(parts)
+    }
+
+    /// Appends the channels of the element at `index` to `data`, whatever
+    /// the layout
+    fn push_element(&self, index: Dimension, data: &mut Vec<F>) -> crate::Result<()> {
+        for channel in 0..self.channels() {
+            let scalar = self.scalar(index, channel)
+                .ok_or(crate::Error::from(Errors::IncompatibleOperandDimensions))?;
+
+            data.push(*scalar);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ ChannelLayout, Dimension, Engine, Graph, Kernel, Tensor, Value };
+
+    /// 3x2 tensor of two channels, element `i` holding `(i, offset + i)`
+    fn sample(offset: i32) -> Tensor<i32> {
+        let data = (0..6).flat_map(|i| [i, offset + i]).collect();
+        Tensor::from_vec(data, Dimension::new(3, 2, 1), 2).unwrap()
+    }
+
+    fn message<T>(result: crate::Result<T>) -> String {
+        match result {
+            Ok(_) => String::from("Ok"),
+            Err(error) => format!("{:?}", error),
+        }
+    }
+
+    /// Evaluates `value` of the parameters `sample(10)` and `sample(20)`
+    fn evaluate(value: &Value) -> Tensor<i32> {
+        let kernel: Kernel<Tensor<i32>, Tensor<i32>, Tensor<i32>> = Engine::new().unwrap().compile(value).unwrap();
+        kernel.run(&sample(10), &sample(20)).unwrap()
+    }
+
+    fn parameters() -> (Value, Value) {
+        let mut graph = Graph::new();
+        let x = graph.tensor::<i32>(Dimension::new(3, 2, 1), 2);
+        let w = graph.tensor::<i32>(Dimension::new(3, 2, 1), 2);
+
+        (x, w)
+    }
+
+    #[test]
+    fn transpose() {
+        let transposed = sample(10).transpose().unwrap();
+
+        assert_eq!(transposed.dimension(), &Dimension::new(2, 3, 1));
+        assert_eq!(&transposed[Dimension::new(1, 2, 0)], &[5, 15]);
+        assert!(transposed.transpose().unwrap() == sample(10));
+
+        let column: Tensor<i32> = (1..4).collect();
+        let row = column.transpose().unwrap();
+        assert_eq!(row.dimension(), &Dimension::new(1, 3, 1));
+        assert_eq!(row.as_slice(), &[1, 2, 3]);
+
+        let planar = sample(10).into_layout(ChannelLayout::Planar).transpose().unwrap();
+        assert_eq!(planar.layout(), ChannelLayout::Interleaved);
+        assert!(planar == transposed);
+    }
+
+    #[test]
+    fn permute() {
+        let permuted = sample(10).permute(&[2, 0, 1]).unwrap();
+
+        assert_eq!(permuted.dimension(), &Dimension::new(1, 3, 2));
+        assert_eq!(&permuted[Dimension::new(0, 2, 1)], &[5, 15]);
+        assert_eq!(message(sample(10).permute(&[0, 0, 1])), "Invalid axis");
+        assert_eq!(message(sample(10).permute(&[1, 0])), "Invalid axis");
+    }
+
+    #[test]
+    fn reshape() {
+        let reshaped = sample(10).reshape(Dimension::new(2, 3, 1)).unwrap();
+
+        assert_eq!(reshaped.as_slice(), sample(10).as_slice());
+        assert_eq!(&reshaped[Dimension::new(1, 1, 0)], &[3, 13]);
+        assert_eq!(message(sample(10).reshape(Dimension::new(4, 2, 1))), "Incompatible operand dimensions");
+        assert_eq!(sample(10).flatten().unwrap().dimension(), &Dimension::new(1, 6, 1));
+    }
+
+    #[test]
+    fn concat() {
+        let (a, b) = (sample(10), sample(20));
+
+        let rows = Tensor::concat(&[&a, &b], 0).unwrap();
+        assert_eq!(rows.dimension(), &Dimension::new(6, 2, 1));
+        assert_eq!(&rows[Dimension::new(4, 1, 0)], &[4, 24]);
+
+        let columns = Tensor::concat(&[&a, &b.clone().into_layout(ChannelLayout::Planar)], 1).unwrap();
+        assert_eq!(columns.dimension(), &Dimension::new(3, 4, 1));
+        assert_eq!(&columns.as_slice()[..12], a.as_slice());
+        assert_eq!(&columns.as_slice()[12..], b.as_slice());
+
+        let planes = Tensor::concat(&[&a, &b, &a], 3).unwrap();
+        assert_eq!(planes.dimension().axes(), &[3, 2, 1, 3]);
+
+        let narrow = Tensor::from_vec(vec![0; 4], Dimension::new(2, 1, 1), 2).unwrap();
+        let single = Tensor::from_vec(vec![0; 6], Dimension::new(3, 2, 1), 1).unwrap();
+        assert_eq!(message(Tensor::concat(&[&a, &narrow], 1)), "Incompatible operand dimensions");
+        assert_eq!(message(Tensor::concat(&[&a, &single], 0)), "Different channel counts");
+        assert_eq!(message(Tensor::<i32>::concat(&[], 0)), "Requires Tensor");
+    }
+
+    #[test]
+    fn stack() {
+        let (a, b) = (sample(10), sample(20));
+
+        let stacked = Tensor::stack(&[&a, &b], 0).unwrap();
+        assert_eq!(stacked.dimension().axes(), &[2, 3, 2, 1]);
+        assert_eq!(&stacked[Dimension::from_axes(&[1, 2, 1, 0]).unwrap()], &[5, 25]);
+
+        let planes = Tensor::stack(&[&a, &b], 2).unwrap();
+        assert_eq!(planes.dimension().axes(), &[3, 2, 2, 1]);
+        assert_eq!(planes.plane(1), Some(b.as_slice()));
+    }
+
+    #[test]
+    fn split() {
+        let joined = Tensor::concat(&[&sample(10), &sample(20)], 1).unwrap();
+        let parts = joined.split(1, &[2, 1, 1]).unwrap();
+
+        assert_eq!(parts.len(), 3);
+        assert!(parts[0] == sample(10));
+        assert_eq!(parts[1].dimension(), &Dimension::new(3, 1, 1));
+        assert_eq!(parts[2].as_slice(), &[3, 23, 4, 24, 5, 25]);
+
+        assert_eq!(message(joined.split(1, &[2, 1])), "Incompatible operand dimensions");
+        assert_eq!(message(joined.split(5, &[1])), "Invalid axis");
+    }
+
+    #[test]
+    fn graph_reshape_and_transpose() {
+        let (x, _) = parameters();
+
+        let reshaped = evaluate(&x.clone().reshape(Dimension::new(2, 3, 1)).unwrap());
+        assert!(reshaped == sample(10).reshape(Dimension::new(2, 3, 1)).unwrap());
+
+        let transposed = evaluate(&x.clone().transpose().unwrap());
+        assert!(transposed == sample(10).transpose().unwrap());
+
+        let permuted = evaluate(&x.permute(&[2, 0, 1]).unwrap());
+        assert!(permuted == sample(10).permute(&[2, 0, 1]).unwrap());
+    }
+
+    #[test]
+    fn graph_concat_and_stack() {
+        let (x, w) = parameters();
+        let (a, b) = (sample(10), sample(20));
+
+        for axis in [0, 1, 2] {
+            let joined = evaluate(&x.clone().concat(w.clone(), axis).unwrap());
+            assert!(joined == Tensor::concat(&[&a, &b], axis).unwrap(), "axis {}", axis);
+
+            let stacked = evaluate(&Value::stack(&[x.clone(), w.clone()], axis).unwrap());
+            assert!(stacked == Tensor::stack(&[&a, &b], axis).unwrap(), "axis {}", axis);
+        }
+    }
+
+    #[test]
+    fn graph_split() {
+        let (x, w) = parameters();
+        let joined = x.concat(w, 1).unwrap();
+        let expected = Tensor::concat(&[&sample(10), &sample(20)], 1).unwrap().split(1, &[1, 3]).unwrap();
+
+        for (part, expected) in joined.split(1, &[1, 3]).unwrap().iter().zip(expected.iter()) {
+            assert!(evaluate(part) == *expected);
+        }
+    }
+}
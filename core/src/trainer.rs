@@ -0,0 +1,417 @@
+use crate::{ Layer, LayerTrainables, LayerValue };
+use crate::codegen::{ GeneralType, ElementType };
+use crate::random::Random;
+use crate::Errors;
+
+/// Fits the trainables of a layer to a dataset of `(input, target)` pairs
+/// by running shuffled mini-batches through a kernel computing the loss,
+/// the predictions and the gradient of every trainable for a whole
+/// mini-batch per call
+pub struct Trainer<L: Layer, O: crate::Optimizer> {
+    layer: L,
+    loss: crate::Loss,
+    optimizer: O,
+    epochs: u32,
+    batch_size: u32,
+    random: Random,
+}
+
+/// Mean loss and accuracy over a batch, or over an epoch when `batch` is
+/// `None`
+#[derive(Clone, Copy)]
+pub struct Metrics {
+    pub epoch: u32,
+    pub batch: Option<u32>,
+    pub loss: f64,
+    pub accuracy: f64,
+}
+
+/// Whether training goes on after a callback
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    Stop,
+}
+
+/// Outputs of the training kernel: summed loss, predictions and summed
+/// gradients
+type Step<F, T> = (crate::Element<F>, (Batch, T));
+
+type StepKernel<F, T> = crate::Kernel<(Batch, Batch), Step<F, T>, T>;
+
+/// Training kernel for batches of one size, with the outputs it writes into
+type Pass<F, T> = (StepKernel<F, T>, Step<F, T>);
+
+/// Samples gathered one after the other into a single interleaved buffer
+/// per `GeneralType`, the way batched kernels read them
+struct Batch {
+    general_types: Vec<GeneralType>,
+    buffers: Vec<Vec<u64>>,
+}
+
+impl<L: Layer, O: crate::Optimizer> Trainer<L, O> {
+    pub fn new(layer: L, loss: crate::Loss, optimizer: O, epochs: u32, batch_size: u32, seed: u64) -> Self {
+        Self {
+            layer,
+            loss,
+            optimizer,
+            epochs,
+            batch_size,
+            random: Random::new(seed),
+        }
+    }
+
+    pub fn layer(&self) -> &L {
+        &self.layer
+    }
+
+    pub fn optimizer(&self) -> &O {
+        &self.optimizer
+    }
+
+    /// Trains `trainables` over `dataset`, calling `callback` after every
+    /// batch and every epoch. Returning `Control::Stop` ends training early.
+    ///
+    /// Accuracy counts a prediction as correct when its largest scalar is
+    /// where the target's is, or for a single scalar when both round alike.
+    pub fn train<'a, X, F>(&mut self, engine: &crate::Engine, trainables: &mut L::Trainables,
+        dataset: impl IntoIterator<Item = &'a (X, crate::Tensor<F>)>, mut callback: impl FnMut(&Metrics) -> Control) -> crate::Result<()>
+        where X: LayerValue + 'a, F: crate::Scalar {
+
+        if self.batch_size == 0 {
+            return Errors::InvalidBatchSize.into();
+        }
+
+        let mut samples: Vec<&(X, crate::Tensor<F>)> = dataset.into_iter().collect();
+
+        let first = match samples.first() {
+            Some(first) => *first,
+            None => return Ok(()),
+        };
+
+        // Every batch but the last is full, so two kernels cover them all
+        let batch_size = (self.batch_size as usize).min(samples.len());
+        let mut full = self.compile(engine, first, trainables, batch_size)?;
+        let mut last = match samples.len() % batch_size {
+            0 => None,
+            remainder => Some(self.compile(engine, first, trainables, remainder)?),
+        };
+
+        let mut gradients = L::Trainables::allocate(&mut trainables.general_types().into_iter())?;
+
+        for epoch in 0..self.epochs {
+            self.random.shuffle(&mut samples);
+
+            let mut epoch_loss = 0.0;
+            let mut epoch_correct = 0;
+
+            for (batch, chunk) in samples.chunks(batch_size).enumerate() {
+                let (kernel, output) = match &mut last {
+                    Some(last) if chunk.len() != batch_size => last,
+                    _ => &mut full,
+                };
+
+                let inputs: Vec<&X> = chunk.iter().map(|sample| &sample.0).collect();
+                let targets: Vec<&crate::Tensor<F>> = chunk.iter().map(|sample| &sample.1).collect();
+                let input = (Batch::gather(&inputs)?, Batch::gather(&targets)?);
+
+                kernel.run_into(&input, trainables, output)?;
+
+                let batch_loss = scalars(&output.0)[0][0];
+                let predictions = &scalars(&output.1.0)[0];
+                let targets = &scalars(&input.1)[0];
+                let size = predictions.len() / chunk.len();
+
+                let batch_correct = predictions.chunks(size)
+                    .zip(targets.chunks(size))
+                    .filter(|(prediction, target)| correct(prediction, target))
+                    .count();
+
+                clear(&mut gradients);
+                add(&mut gradients, &output.1.1, 1.0 / chunk.len() as f64);
+
+                self.optimizer.step(trainables, &gradients)?;
+
+                epoch_loss += batch_loss;
+                epoch_correct += batch_correct;
+
+                let metrics = Metrics {
+                    epoch,
+                    batch: Some(batch as u32),
+                    loss: batch_loss / chunk.len() as f64,
+                    accuracy: batch_correct as f64 / chunk.len() as f64,
+                };
+
+                if callback(&metrics) == Control::Stop {
+                    return Ok(());
+                }
+            }
+
+            let metrics = Metrics {
+                epoch,
+                batch: None,
+                loss: epoch_loss / samples.len() as f64,
+                accuracy: epoch_correct as f64 / samples.len() as f64,
+            };
+
+            if callback(&metrics) == Control::Stop {
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Kernel taking `batch` samples shaped like `sample`, whose inputs and
+    /// targets are parameters 0 and 1, then the trainables, along with the
+    /// outputs it writes into
+    fn compile<X, F>(&self, engine: &crate::Engine, sample: &(X, crate::Tensor<F>), trainables: &L::Trainables, batch: usize)
+        -> crate::Result<Pass<F, L::Trainables>>
+        where X: LayerValue, F: crate::Scalar {
+
+        let input_types = sample.0.general_types();
+
+        if input_types.len() != 1 {
+            return Errors::IncompatibleOperandDimensions.into();
+        }
+
+        let batch = batch as u32;
+        let mut graph = crate::Graph::new();
+        let input = graph.batched_input(input_types[0], batch)?;
+        let target = graph.batched_input(sample.1.general_types()[0], batch)?;
+
+        let parameters = graph.like(trainables);
+        let prediction = self.layer.operations(input, trainables.values(2))?;
+        let loss = prediction.clone().loss(target, &self.loss)?.batch_sum()?;
+        let gradients = loss.gradient(&parameters.iter().collect::<Vec<_>>())?;
+
+        let mut outputs = vec![&loss, &prediction];
+        outputs.extend(gradients.iter());
+
+        let kernel = engine.compile_outputs(&outputs)?;
+        let output = LayerValue::allocate(&mut kernel_outputs(&sample.1, batch, trainables)?.into_iter())?;
+
+        Ok((kernel, output))
+    }
+}
+
+impl Batch {
+    /// Copies `samples`, which have to share their types, one after the
+    /// other
+    fn gather<V: LayerValue>(samples: &[&V]) -> crate::Result<Self> {
+        let general_types = samples.first()
+            .map(|sample| sample.general_types())
+            .unwrap_or_default();
+
+        let mut buffers: Vec<Vec<u64>> = general_types.iter()
+            .map(|general_type| vec![0; (general_type.scalars() * general_type.scalar_type().size() * samples.len()).div_ceil(8)])
+            .collect();
+
+        for (k, sample) in samples.iter().enumerate() {
+            if sample.general_types() != general_types {
+                return Errors::DifferentOperandDimensions.into();
+            }
+
+            for (i, (source, layout)) in sample.buffers().into_iter().zip(sample.layouts()).enumerate() {
+                let general_type = &general_types[i];
+                let size = general_type.scalars() * general_type.scalar_type().size();
+
+                unsafe {
+                    let destination = (buffers[i].as_mut_ptr() as *mut u8).add(k * size);
+
+                    match layout {
+                        crate::ChannelLayout::Interleaved => destination.copy_from_nonoverlapping(source, size),
+                        crate::ChannelLayout::Planar => crate::kernel::rearrange(source, destination, general_type,
+                            crate::ChannelLayout::Interleaved),
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            general_types: general_types.iter()
+                .map(|general_type| general_type.batched(samples.len() as u32))
+                .collect::<crate::Result<_>>()?,
+            buffers,
+        })
+    }
+}
+
+impl LayerValue for Batch {
+    fn general_types(&self) -> Vec<GeneralType> {
+        self.general_types.clone()
+    }
+
+    fn buffers(&self) -> Vec<*const u8> {
+        self.buffers.iter().map(|buffer| buffer.as_ptr() as _).collect()
+    }
+
+    fn buffers_mut(&mut self) -> Vec<*mut u8> {
+        self.buffers.iter_mut().map(|buffer| buffer.as_mut_ptr() as _).collect()
+    }
+
+    fn allocate(general_types: &mut dyn Iterator<Item = GeneralType>) -> crate::Result<Self> {
+        let general_type = general_types.next().ok_or(crate::Error::from(Errors::InvalidKernelOutput))?;
+        let bytes = general_type.scalars() * general_type.scalar_type().size();
+
+        Ok(Self {
+            general_types: vec![general_type],
+            buffers: vec![vec![0; bytes.div_ceil(8)]],
+        })
+    }
+}
+
+/// Types of the outputs of a training kernel over `batch` samples, in
+/// order
+fn kernel_outputs<F: crate::Scalar, T: LayerTrainables>(target: &crate::Tensor<F>, batch: u32, trainables: &T) -> crate::Result<Vec<GeneralType>> {
+    let mut general_types = vec![GeneralType::Element(ElementType(1, F::scalar_type()))];
+
+    for general_type in target.general_types() {
+        general_types.push(general_type.batched(batch)?);
+    }
+
+    general_types.extend(trainables.general_types());
+    Ok(general_types)
+}
+
+/// Scalars of every buffer of `value`
+fn scalars<V: LayerValue>(value: &V) -> Vec<Vec<f64>> {
+    value.general_types()
+        .iter()
+        .zip(value.buffers())
+        .map(|(general_type, buffer)| {
+            let scalar = general_type.scalar_type();
+            (0..general_type.scalars()).map(|i| unsafe { scalar.read(buffer, i) }).collect()
+        })
+        .collect()
+}
+
+fn clear<V: LayerValue>(value: &mut V) {
+    for (general_type, buffer) in value.general_types().iter().zip(value.buffers_mut()) {
+        let scalar = general_type.scalar_type();
+
+        for i in 0..general_type.scalars() {
+            unsafe { scalar.write(buffer, i, 0.0) };
+        }
+    }
+}
+
+/// Adds `value` scaled by `scale` onto `total`
+fn add<V: LayerValue>(total: &mut V, value: &V, scale: f64) {
+    let values = scalars(value);
+
+    for ((general_type, buffer), values) in total.general_types().iter().zip(total.buffers_mut()).zip(values) {
+        let scalar = general_type.scalar_type();
+
+        for (i, value) in values.into_iter().enumerate() {
+            unsafe { scalar.write(buffer, i, scalar.read(buffer, i) + value * scale) };
+        }
+    }
+}
+
+fn correct(prediction: &[f64], target: &[f64]) -> bool {
+    if prediction.len() == 1 {
+        return prediction[0].round() == target[0].round();
+    }
+
+    argmax(prediction) == argmax(target)
+}
+
+fn argmax(values: &[f64]) -> usize {
+    values.iter()
+        .enumerate()
+        .fold((0, f64::NEG_INFINITY), |best, (i, value)| if *value > best.1 { (i, *value) } else { best })
+        .0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ Control, Trainer };
+    use crate::layers::{ Dense, Initializer };
+    use crate::optimizers::Sgd;
+    use crate::{ Dimension, Engine, Tensor };
+
+    type Sample = (Tensor<f64>, Tensor<f64>);
+
+    /// Samples of `y = 2x - 1`
+    fn line() -> Vec<Sample> {
+        (0..8)
+            .map(|i| {
+                let x = i as f64 / 4.0 - 1.0;
+
+                (Tensor::full(x, Dimension::new(1, 1, 1), 1).unwrap(),
+                    Tensor::full(2.0 * x - 1.0, Dimension::new(1, 1, 1), 1).unwrap())
+            })
+            .collect()
+    }
+
+    fn message(result: crate::Result<()>) -> String {
+        match result {
+            Ok(_) => String::from("Ok"),
+            Err(error) => format!("{:?}", error),
+        }
+    }
+
+    #[test]
+    fn fits_a_line() {
+        let engine = Engine::new().unwrap();
+        let layer = Dense::<f64>::new(1, 1, None);
+        let mut trainables = layer.trainables(Initializer::Zeros, 0).unwrap();
+        let mut trainer = Trainer::new(layer, crate::Loss::MeanSquaredError, Sgd::new(0.1), 200, 4, 1);
+
+        let mut losses = Vec::new();
+        trainer.train(&engine, &mut trainables, &line(), |metrics| {
+            if metrics.batch.is_none() {
+                losses.push(metrics.loss);
+            }
+
+            Control::Continue
+        }).unwrap();
+
+        assert_eq!(losses.len(), 200);
+        assert!(losses[199] < 1e-4 * losses[0], "{} -> {}", losses[0], losses[199]);
+    }
+
+    #[test]
+    fn stops_early() {
+        let engine = Engine::new().unwrap();
+        let layer = Dense::<f64>::new(1, 1, None);
+        let mut trainables = layer.trainables(Initializer::Zeros, 0).unwrap();
+        let mut trainer = Trainer::new(layer, crate::Loss::MeanSquaredError, Sgd::new(0.1), 10, 3, 1);
+
+        let mut calls = 0;
+        trainer.train(&engine, &mut trainables, &line(), |_| {
+            calls += 1;
+            Control::Stop
+        }).unwrap();
+
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn rejects_empty_batches() {
+        let engine = Engine::new().unwrap();
+        let layer = Dense::<f64>::new(1, 1, None);
+        let mut trainables = layer.trainables(Initializer::Zeros, 0).unwrap();
+        let mut trainer = Trainer::new(layer, crate::Loss::MeanSquaredError, Sgd::new(0.1), 1, 0, 1);
+
+        let result = trainer.train(&engine, &mut trainables, &line(), |_| Control::Continue);
+        assert_eq!(message(result), "Invalid batch size");
+    }
+
+    #[test]
+    fn rejects_inputs_of_several_buffers() {
+        let engine = Engine::new().unwrap();
+        let layer = Dense::<f64>::new(1, 1, None);
+        let mut trainables = layer.trainables(Initializer::Zeros, 0).unwrap();
+        let mut trainer = Trainer::new(layer, crate::Loss::MeanSquaredError, Sgd::new(0.1), 1, 2, 1);
+
+        let dataset: Vec<(Sample, Tensor<f64>)> = line().into_iter()
+            .map(|(x, y)| ((x.clone(), x), y))
+            .collect();
+
+        let result = trainer.train(&engine, &mut trainables, &dataset, |_| Control::Continue);
+        assert_eq!(message(result), "Incompatible operand dimensions");
+    }
+}